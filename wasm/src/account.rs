@@ -0,0 +1,101 @@
+use super::*;
+use rand::rngs::OsRng;
+use snarkvm_console::account::ViewKey;
+
+/// An issuer account: a private key with its view key and address.
+#[derive(Debug)]
+pub struct IssuerAccount<N: NetworkNative> {
+    private_key: SecretPrivateKey<N>,
+    view_key: ViewKey<N>,
+    address: Address<N>,
+}
+
+impl<N: NetworkNative> IssuerAccount<N> {
+    /// Creates the account of the private key.
+    pub fn new(private_key: SecretPrivateKey<N>) -> Result<Self, CustomError> {
+        let view_key = ViewKey::<N>::try_from(private_key.expose())
+            .map_err(|e| anyhow!("Failed to derive view key: {}", e))?;
+        let address = Address::<N>::try_from(&view_key)
+            .map_err(|e| anyhow!("Failed to derive address: {}", e))?;
+        Ok(Self { private_key, view_key, address })
+    }
+
+    /// Returns the private key.
+    pub fn private_key(&self) -> &SecretPrivateKey<N> {
+        &self.private_key
+    }
+
+    /// Returns the view key.
+    pub fn view_key(&self) -> &ViewKey<N> {
+        &self.view_key
+    }
+
+    /// Returns the address.
+    pub fn address(&self) -> Address<N> {
+        self.address
+    }
+
+    /// Turns the account into a signer for its private key.
+    pub fn into_signer(self) -> Result<PrivateKeySigner<N>, CustomError> {
+        PrivateKeySigner::new(self.private_key)
+    }
+
+    /// Samples a new random account.
+    pub fn generate() -> Result<Self, CustomError> {
+        let private_key = PrivateKey::<N>::new(&mut OsRng)
+            .map_err(|e| anyhow!("Failed to generate private key: {}", e))?;
+        Self::new(SecretPrivateKey::new(private_key))
+    }
+
+    /// Parses the account of a secret `APrivateKey1...` string.
+    pub fn from_secret(private_key: &SecretString) -> Result<Self, CustomError> {
+        Self::new(SecretPrivateKey::from_secret(private_key)?)
+    }
+}
+
+/// Parses an address after trimming surrounding whitespace and lowercasing it.
+///
+/// Bech32 addresses are case-insensitive as long as they are not mixed-case, so an
+/// all-uppercase address is accepted and normalised, while a mixed-case one is rejected.
+pub fn parse_address_normalized<N: NetworkNative>(address: &str) -> Result<Address<N>, CustomError> {
+    let address = address.trim();
+    if address.chars().any(|c| c.is_ascii_uppercase()) && address.chars().any(|c| c.is_ascii_lowercase()) {
+        return Err(anyhow!("Address must not be mixed-case: {}", address).into());
+    }
+    Ok(Address::<N>::from_str(&address.to_ascii_lowercase())
+        .map_err(|e| anyhow!("Invalid address {}: {}", address, e))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, ISSUER};
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    #[test]
+    fn test_generate_account() {
+        let account = IssuerAccount::<N>::generate().unwrap();
+        let other = IssuerAccount::<N>::generate().unwrap();
+        assert_ne!(account.address(), other.address());
+        assert_eq!(account.address(), Address::<N>::try_from(account.view_key()).unwrap());
+        assert_eq!(account.address(), Address::<N>::try_from(account.private_key().expose()).unwrap());
+    }
+
+    #[test]
+    fn test_account_from_private_key() {
+        let account = IssuerAccount::<N>::from_secret(&SecretString::new(PRIVATE_KEY.to_string())).unwrap();
+        assert_eq!(account.address().to_string(), ISSUER);
+        assert!(account.view_key().to_string().starts_with("AViewKey1"));
+    }
+
+    #[test]
+    fn test_parse_address_normalized() {
+        let address = Address::<N>::from_str(ISSUER).unwrap();
+        assert_eq!(parse_address_normalized::<N>(&format!("  {}\n", ISSUER)).unwrap(), address);
+        assert_eq!(parse_address_normalized::<N>(&ISSUER.to_ascii_uppercase()).unwrap(), address);
+        assert!(parse_address_normalized::<N>(&ISSUER.replacen("ekyu", "EKYU", 1)).is_err());
+        assert!(parse_address_normalized::<N>("aleo1invalid").is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use super::*;
+
+/// The credential attribute holding the issuer address.
+pub const ISSUER_ATTRIBUTE: &str = "issuer";
+/// The credential attribute holding the subject address.
+pub const SUBJECT_ATTRIBUTE: &str = "subject";
+
+/// The checks run on a credential before it is signed.
+///
+/// A credential failing them would produce a signature the `issue` transition rejects, so by
+/// default the issuer and subject attributes are required, the issuer must be the signing key's
+/// address and the subject must be a valid address. Credentials that have already expired are
+/// refused too: at the reference time if one is given, otherwise at today's date when the expiry
+/// is a `YYYYMMDD` date.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignChecks {
+    pub(crate) issuer_matches_key: bool,
+    pub(crate) subject_is_address: bool,
+    pub(crate) required_attributes: Vec<String>,
+    pub(crate) reference_time: Option<ReferenceTime>,
+    pub(crate) expiry_at_today: bool,
+}
+
+impl Default for SignChecks {
+    fn default() -> Self {
+        Self {
+            issuer_matches_key: true,
+            subject_is_address: true,
+            required_attributes: vec![ISSUER_ATTRIBUTE.to_string(), SUBJECT_ATTRIBUTE.to_string()],
+            reference_time: None,
+            expiry_at_today: true,
+        }
+    }
+}
+
+impl SignChecks {
+    /// The default checks without the check against today's date, for credentials that were
+    /// already signed: verifiers check the validity window at the time they choose instead.
+    pub fn verification() -> Self {
+        Self { expiry_at_today: false, ..Self::default() }
+    }
+
+    /// Disables every check.
+    pub fn none() -> Self {
+        Self { issuer_matches_key: false, subject_is_address: false, required_attributes: Vec::new(), reference_time: None, expiry_at_today: false }
+    }
+
+    /// Requires the attribute to be present.
+    pub fn require_attribute(mut self, name: &str) -> Self {
+        self.required_attributes.push(name.to_string());
+        self
+    }
+
+    /// Refuses credentials that have expired at the reference time.
+    pub fn at(mut self, reference_time: ReferenceTime) -> Self {
+        self.reference_time = Some(reference_time);
+        self
+    }
+
+    /// Runs the checks on the credential data, for a credential signed by `issuer`.
+    pub fn check<N: NetworkNative>(&self, data: &JsonValue, issuer: &Address<N>) -> Result<(), CustomError> {
+        let attributes = data.as_object().ok_or(CustomError::InvalidCredentialData)?;
+
+        if let Some(name) = self.required_attributes.iter().find(|name| !attributes.contains_key(name.as_str())) {
+            return Err(CustomError::MissingAttribute(name.clone()));
+        }
+
+        if self.issuer_matches_key {
+            if let Some(value) = attributes.get(ISSUER_ATTRIBUTE) {
+                let matches = value.as_str()
+                    .and_then(|value| Address::<N>::from_str(value).ok())
+                    .is_some_and(|address| address == *issuer);
+                if !matches {
+                    return Err(CustomError::IssuerMismatch { expected: issuer.to_string(), found: display_attribute(value) });
+                }
+            }
+        }
+
+        if self.subject_is_address {
+            if let Some(value) = attributes.get(SUBJECT_ATTRIBUTE) {
+                if value.as_str().and_then(|value| Address::<N>::from_str(value).ok()).is_none() {
+                    return Err(CustomError::InvalidSubject(display_attribute(value)));
+                }
+            }
+        }
+
+        match self.reference_time {
+            Some(time) => Validity::from_data(data, time.basis)?.check_not_expired(time.value)?,
+            // Expiries that are not dates, such as block heights, need an explicit reference time.
+            None if self.expiry_at_today => {
+                if let Ok(validity) = Validity::from_data(data, TimeBasis::Date) {
+                    validity.check_not_expired(ReferenceTime::today()?.value)?;
+                }
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[wasm_bindgen]
+impl SignChecks {
+    /// Creates the default checks.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> SignChecks {
+        SignChecks::default()
+    }
+
+    /// Returns whether the issuer attribute must be the signing key's address.
+    #[wasm_bindgen(getter, js_name = issuerMatchesKey)]
+    pub fn issuer_matches_key(&self) -> bool {
+        self.issuer_matches_key
+    }
+
+    /// Sets whether the issuer attribute must be the signing key's address.
+    #[wasm_bindgen(setter, js_name = issuerMatchesKey)]
+    pub fn set_issuer_matches_key(&mut self, enabled: bool) {
+        self.issuer_matches_key = enabled;
+    }
+
+    /// Returns whether the subject attribute must be a valid address.
+    #[wasm_bindgen(getter, js_name = subjectIsAddress)]
+    pub fn subject_is_address(&self) -> bool {
+        self.subject_is_address
+    }
+
+    /// Sets whether the subject attribute must be a valid address.
+    #[wasm_bindgen(setter, js_name = subjectIsAddress)]
+    pub fn set_subject_is_address(&mut self, enabled: bool) {
+        self.subject_is_address = enabled;
+    }
+
+    /// Returns the attributes that must be present.
+    #[wasm_bindgen(getter, js_name = requiredAttributes)]
+    pub fn required_attributes(&self) -> Vec<String> {
+        self.required_attributes.clone()
+    }
+
+    /// Sets the attributes that must be present.
+    #[wasm_bindgen(setter, js_name = requiredAttributes)]
+    pub fn set_required_attributes(&mut self, names: Vec<String>) {
+        self.required_attributes = names;
+    }
+
+    /// Returns the time expiry is checked at, if any.
+    #[wasm_bindgen(getter, js_name = referenceTime)]
+    pub fn reference_time(&self) -> Option<ReferenceTime> {
+        self.reference_time
+    }
+
+    /// Sets the time expiry is checked at, or falls back to `expiryAtToday`.
+    #[wasm_bindgen(setter, js_name = referenceTime)]
+    pub fn set_reference_time(&mut self, reference_time: Option<ReferenceTime>) {
+        self.reference_time = reference_time;
+    }
+
+    /// Returns whether date expiries are checked at today's date when no reference time is set.
+    #[wasm_bindgen(getter, js_name = expiryAtToday)]
+    pub fn expiry_at_today(&self) -> bool {
+        self.expiry_at_today
+    }
+
+    /// Sets whether date expiries are checked at today's date when no reference time is set.
+    #[wasm_bindgen(setter, js_name = expiryAtToday)]
+    pub fn set_expiry_at_today(&mut self, enabled: bool) {
+        self.expiry_at_today = enabled;
+    }
+}
+
+// Shows string attributes without their JSON quotes.
+fn display_attribute(value: &JsonValue) -> String {
+    value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ISSUER, SUBJECT};
+    use serde_json::json;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    fn issuer() -> Address<N> {
+        Address::<N>::from_str(ISSUER).unwrap()
+    }
+
+    #[test]
+    fn test_default_checks() {
+        let checks = SignChecks::default();
+        assert!(checks.check(&json!({ "issuer": ISSUER, "subject": SUBJECT, "dob": "20000101u32" }), &issuer()).is_ok());
+        assert!(matches!(
+            checks.check(&json!({ "subject": SUBJECT, "dob": "20000101u32" }), &issuer()),
+            Err(CustomError::MissingAttribute(name)) if name == ISSUER_ATTRIBUTE
+        ));
+        assert!(matches!(
+            checks.check(&json!({ "issuer": ISSUER, "dob": "20000101u32" }), &issuer()),
+            Err(CustomError::MissingAttribute(name)) if name == SUBJECT_ATTRIBUTE
+        ));
+
+        assert!(matches!(
+            checks.check(&json!({ "issuer": SUBJECT, "subject": SUBJECT }), &issuer()),
+            Err(CustomError::IssuerMismatch { expected, found }) if expected == ISSUER && found == SUBJECT
+        ));
+        assert!(matches!(
+            checks.check(&json!({ "issuer": ISSUER, "subject": "123field" }), &issuer()),
+            Err(CustomError::InvalidSubject(subject)) if subject == "123field"
+        ));
+        assert!(matches!(checks.check(&json!(["issuer"]), &issuer()), Err(CustomError::InvalidCredentialData)));
+    }
+
+    #[test]
+    fn test_required_and_disabled_checks() {
+        let checks = SignChecks::default().require_attribute("expiry");
+        assert!(matches!(
+            checks.check(&json!({ "issuer": ISSUER, "subject": SUBJECT }), &issuer()),
+            Err(CustomError::MissingAttribute(name)) if name == "expiry"
+        ));
+        assert!(checks.at(ReferenceTime::date(20250101).unwrap()).check(&json!({ "issuer": ISSUER, "subject": SUBJECT, "expiry": "20300101u32" }), &issuer()).is_ok());
+
+        assert!(SignChecks::none().check(&json!({ "issuer": SUBJECT, "subject": "123field" }), &issuer()).is_ok());
+        assert!(SignChecks::none().check(&json!({ "dob": "20000101u32" }), &issuer()).is_ok());
+    }
+
+    #[test]
+    fn test_expired_credentials_are_refused_by_default() {
+        let checks = SignChecks::default();
+        let expired = json!({ "issuer": ISSUER, "subject": SUBJECT, "expiry": "20200101u32" });
+        assert!(matches!(checks.check(&expired, &issuer()), Err(CustomError::Expired { expiry: 20200101, .. })));
+        assert!(SignChecks::default().at(ReferenceTime::date(20191231).unwrap()).check(&expired, &issuer()).is_ok());
+
+        // Block height expiries are only checked at an explicit reference time.
+        let height = json!({ "issuer": ISSUER, "subject": SUBJECT, "expiry": "5000000u32" });
+        assert!(checks.check(&height, &issuer()).is_ok());
+        assert!(checks.clone().at(ReferenceTime::block_height(5000001)).check(&height, &issuer()).is_err());
+
+        let mut lenient = SignChecks::default();
+        lenient.set_expiry_at_today(false);
+        assert!(lenient.check(&expired, &issuer()).is_ok());
+        assert!(SignChecks::none().check(&expired, &issuer()).is_ok());
+        assert!(SignChecks::verification().check(&expired, &issuer()).is_ok());
+    }
+
+    #[test]
+    fn test_expired_credentials_are_refused() {
+        let data = json!({ "issuer": ISSUER, "subject": SUBJECT, "expiry": "20300101u32" });
+        assert!(SignChecks::default().at(ReferenceTime::date(20250101).unwrap()).check(&data, &issuer()).is_ok());
+        assert!(SignChecks::default().at(ReferenceTime::date(20300101).unwrap()).check(&data, &issuer()).is_ok());
+        assert!(matches!(
+            SignChecks::default().at(ReferenceTime::date(20300102).unwrap()).check(&data, &issuer()),
+            Err(CustomError::Expired { expiry: 20300101, time: 20300102 })
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+use super::*;
+use rand::rngs::OsRng;
+use crate::program::literal_from_json;
+use crate::transition::full_credentials;
+
+/// The attributes the `zpass_hiding` records store as commitments, with their types, in record order.
+pub const HIDDEN_ATTRIBUTES: [(&str, LiteralType); 4] = [
+    (ISSUER_ATTRIBUTE, LiteralType::Address),
+    ("dob", LiteralType::U32),
+    ("nationality", LiteralType::Field),
+    ("expiry", LiteralType::U32),
+];
+
+/// Generates a random credential salt, as passed to the `issue` transitions in `PublicCredentials`.
+pub fn generate_salt<N: NetworkNative>() -> Scalar<N> {
+    Scalar::rand(&mut OsRng)
+}
+
+/// Parses a salt such as `1234scalar`.
+pub fn parse_salt<N: NetworkNative>(salt: &str) -> Result<Scalar<N>, CustomError> {
+    Ok(Scalar::<N>::from_str(salt.trim()).map_err(|e| anyhow!("Invalid salt '{}': {}", salt, e))?)
+}
+
+/// Commits to the value with the salt, as Leo's `BHP256::commit_to_group(value, salt)` does.
+pub(crate) fn commit_to_group_bhp256<N: NetworkNative>(value: Plaintext<N>, salt: &Scalar<N>) -> Result<Group<N>, CustomError> {
+    let bits = Value::Plaintext(value).to_bits_le();
+    Ok(N::commit_to_group_bhp256(&bits, salt).map_err(|e| anyhow!("Failed commit_to_group_bhp256 conversion: {}", e))?)
+}
+
+/// Computes the issuance commitment `BHP256::commit_to_group(self.caller, pub.salt)` of the `issue`
+/// transitions, i.e. the key the `issued` mapping stores for the subject and salt.
+///
+/// An issuer can look the commitment up in the mapping before submitting, as a salt that was already
+/// issued to the subject makes the transition fail.
+pub fn issuance_commitment<N: NetworkNative>(subject: &Address<N>, salt: &Scalar<N>) -> Result<Group<N>, CustomError> {
+    commit_to_group_bhp256(Plaintext::from(Literal::Address(*subject)), salt)
+}
+
+/// Computes the per-attribute commitments `BHP256::commit_to_group(value, salt)` a `zpass_hiding` or
+/// `zpass_invalidate_hiding` record stores for the credential, keyed by attribute in record order.
+pub fn hiding_commitments<N: NetworkNative>(data: &JsonValue, salt: &Scalar<N>) -> Result<IndexMap<String, Group<N>>, CustomError> {
+    let data = data.as_object().ok_or_else(|| anyhow!("Credential data must be a JSON object"))?;
+    HIDDEN_ATTRIBUTES
+        .iter()
+        .map(|(name, literal_type)| {
+            let value = data.get(*name).ok_or_else(|| anyhow!("Missing '{}' of type '{}'", name, literal_type))?;
+            let literal = literal_from_json::<N>(*literal_type, value, name)?;
+            Ok((name.to_string(), commit_to_group_bhp256(Plaintext::from(literal), salt)?))
+        })
+        .collect()
+}
+
+/// Verifies the opening `(value, salt)` of a record's attribute commitment, as `more_than_18` does
+/// with `assert_eq(zpass.dob, BHP256::commit_to_group(dob, zpass.salt))`.
+///
+/// The value is committed with its type, so `20000101u32` and `20000101u64` open different commitments.
+pub fn verify_opening<N: NetworkNative>(commitment: &Group<N>, value: &Literal<N>, salt: &Scalar<N>) -> Result<bool, CustomError> {
+    Ok(commit_to_group_bhp256(Plaintext::from(value.clone()), salt)? == *commitment)
+}
+
+/// Computes the invalidation commitment `BHP256::commit_to_group(credentials, salt)` of the
+/// `zpass_invalidate` programs over the `FullCredentials` struct of the credential and salt, i.e. the
+/// key `invalidate` sets in the `invalidated` mapping and `is_invalid` looks up.
+pub fn invalidation_commitment<N: NetworkNative>(data: &JsonValue, salt: &Scalar<N>) -> Result<Group<N>, CustomError> {
+    commit_to_group_bhp256(full_credentials(data, salt)?, salt)
+}
+
+/// Computes the invalidation commitment of the `zpass_invalidate_hiding` program, which commits to the
+/// `InvalidateZPass` struct of the record: the attribute commitments, the subject and the salt.
+pub fn hiding_invalidation_commitment<N: NetworkNative>(data: &JsonValue, salt: &Scalar<N>) -> Result<Group<N>, CustomError> {
+    let commitments = hiding_commitments(data, salt)?;
+    let subject = data.get(SUBJECT_ATTRIBUTE).ok_or_else(|| anyhow!("Missing '{}' of type 'address'", SUBJECT_ATTRIBUTE))?;
+    let subject = literal_from_json::<N>(LiteralType::Address, subject, SUBJECT_ATTRIBUTE)?;
+
+    // The members of `InvalidateZPass`, in its member order.
+    let mut members = IndexMap::new();
+    insert_to_map(&mut members, ISSUER_ATTRIBUTE, Plaintext::from(Literal::Group(commitments[ISSUER_ATTRIBUTE])))?;
+    insert_to_map(&mut members, SUBJECT_ATTRIBUTE, Plaintext::from(subject))?;
+    for name in ["dob", "nationality", "expiry"] {
+        insert_to_map(&mut members, name, Plaintext::from(Literal::Group(commitments[name])))?;
+    }
+    insert_to_map(&mut members, "salt", Plaintext::from(Literal::Scalar(*salt)))?;
+    commit_to_group_bhp256(Plaintext::Struct(members, Default::default()), salt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, ISSUER, SUBJECT, credentials};
+    use snarkvm_circuit::AleoTestnetV0;
+    use snarkvm_synthesizer_process::Process;
+    use snarkvm_synthesizer_program::Program;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    // The `commit.bhp256` instructions the zPass transitions compile to, on the same operands.
+    const COMMITMENTS_PROGRAM: &str = "
+program zpass_commitments.aleo;
+
+struct FullCredentials:
+    issuer as address;
+    subject as address;
+    dob as u32;
+    nationality as field;
+    expiry as u32;
+    salt as scalar;
+
+struct InvalidateZPass:
+    issuer as group;
+    subject as address;
+    dob as group;
+    nationality as group;
+    expiry as group;
+    salt as scalar;
+
+function issuance:
+    input r0 as address.private;
+    input r1 as scalar.private;
+    commit.bhp256 r0 r1 into r2 as group;
+    output r2 as group.private;
+
+function hiding:
+    input r0 as address.private;
+    input r1 as u32.private;
+    input r2 as field.private;
+    input r3 as u32.private;
+    input r4 as scalar.private;
+    commit.bhp256 r0 r4 into r5 as group;
+    commit.bhp256 r1 r4 into r6 as group;
+    commit.bhp256 r2 r4 into r7 as group;
+    commit.bhp256 r3 r4 into r8 as group;
+    output r5 as group.private;
+    output r6 as group.private;
+    output r7 as group.private;
+    output r8 as group.private;
+
+function invalidation:
+    input r0 as FullCredentials.private;
+    commit.bhp256 r0 r0.salt into r1 as group;
+    output r1 as group.private;
+
+function hiding_invalidation:
+    input r0 as InvalidateZPass.private;
+    commit.bhp256 r0 r0.salt into r1 as group;
+    output r1 as group.private;
+";
+
+    /// Evaluates a function of the commitments program through a snarkVM process, as `leo run` does.
+    fn evaluate(function: &str, inputs: &[String]) -> Vec<String> {
+        let program = Program::<N>::from_str(COMMITMENTS_PROGRAM).unwrap();
+        let mut process = Process::<N>::load().unwrap();
+        process.add_program(&program).unwrap();
+        let private_key = PrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let inputs = inputs.iter().map(String::as_str);
+        let authorization = process.authorize::<AleoTestnetV0, _>(&private_key, program.id(), function, inputs, &mut OsRng).unwrap();
+        let response = process.evaluate::<AleoTestnetV0>(authorization).unwrap();
+        response.outputs().iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_generate_salt() {
+        let salt = generate_salt::<N>();
+        assert_ne!(salt, generate_salt::<N>());
+        assert_eq!(parse_salt::<N>(&salt.to_string()).unwrap(), salt);
+        assert!(parse_salt::<N>("1234field").is_err());
+    }
+
+    #[test]
+    fn test_issuance_commitment() {
+        let subject = Address::<N>::from_str(SUBJECT).unwrap();
+        let salt = parse_salt::<N>("1234scalar").unwrap();
+        let commitment = issuance_commitment(&subject, &salt).unwrap();
+        assert_eq!(commitment.to_string(), evaluate("issuance", &[SUBJECT.to_string(), "1234scalar".to_string()])[0]);
+
+        // The commit instruction commits to the bits of the address value.
+        let bits = Value::<N>::from_str(SUBJECT).unwrap().to_bits_le();
+        assert_eq!(commitment, N::commit_to_group_bhp256(&bits, &salt).unwrap());
+
+        assert_eq!(commitment, issuance_commitment(&subject, &salt).unwrap());
+        assert_ne!(commitment, issuance_commitment(&subject, &parse_salt("1235scalar").unwrap()).unwrap());
+        assert_ne!(commitment, issuance_commitment(&Address::from_str(ISSUER).unwrap(), &salt).unwrap());
+    }
+
+    #[test]
+    fn test_hiding_commitments_open() {
+        let salt = parse_salt::<N>("1234scalar").unwrap();
+        let commitments = hiding_commitments::<N>(&credentials(), &salt).unwrap();
+        assert_eq!(commitments.keys().collect::<Vec<_>>(), ["issuer", "dob", "nationality", "expiry"]);
+        let inputs = [ISSUER, "20000101u32", "840field", "20300101u32", "1234scalar"].map(String::from);
+        let expected = evaluate("hiding", &inputs);
+        for (name, expected) in commitments.keys().zip(expected) {
+            assert_eq!(commitments[name].to_string(), expected, "{}", name);
+        }
+
+        let dob = Literal::<N>::from_str("20000101u32").unwrap();
+        assert!(verify_opening(&commitments["dob"], &dob, &salt).unwrap());
+        assert!(verify_opening(&commitments["issuer"], &Literal::from_str(ISSUER).unwrap(), &salt).unwrap());
+        assert!(!verify_opening(&commitments["dob"], &Literal::from_str("20000101u64").unwrap(), &salt).unwrap());
+        assert!(!verify_opening(&commitments["dob"], &Literal::from_str("20000102u32").unwrap(), &salt).unwrap());
+        assert!(!verify_opening(&commitments["dob"], &dob, &parse_salt("1235scalar").unwrap()).unwrap());
+        assert!(!verify_opening(&commitments["expiry"], &dob, &salt).unwrap());
+    }
+
+    #[test]
+    fn test_hiding_commitments_type_check() {
+        let salt = parse_salt::<N>("1234scalar").unwrap();
+        let mut data = credentials();
+        data["dob"] = serde_json::json!("20000101u64");
+        assert!(hiding_commitments::<N>(&data, &salt).is_err());
+        data.as_object_mut().unwrap().remove("dob");
+        assert!(hiding_commitments::<N>(&data, &salt).is_err());
+    }
+
+    #[test]
+    fn test_invalidation_commitment() {
+        let salt = parse_salt::<N>("1234scalar").unwrap();
+        let commitment = invalidation_commitment::<N>(&credentials(), &salt).unwrap();
+
+        // The struct `invalidate` builds from the record, in its member order.
+        let full_credentials = format!(
+            "{{ issuer: {}, subject: {}, dob: 20000101u32, nationality: 840field, expiry: 20300101u32, salt: 1234scalar }}",
+            ISSUER, SUBJECT
+        );
+        assert_eq!(commitment.to_string(), evaluate("invalidation", &[full_credentials])[0]);
+        assert_ne!(commitment, invalidation_commitment::<N>(&credentials(), &parse_salt("1235scalar").unwrap()).unwrap());
+
+        let mut data = credentials();
+        data.as_object_mut().unwrap().remove("subject");
+        assert!(invalidation_commitment::<N>(&data, &salt).is_err());
+    }
+
+    #[test]
+    fn test_hiding_invalidation_commitment() {
+        let salt = parse_salt::<N>("1234scalar").unwrap();
+        let commitments = hiding_commitments::<N>(&credentials(), &salt).unwrap();
+        let record = format!(
+            "{{ issuer: {}, subject: {}, dob: {}, nationality: {}, expiry: {}, salt: 1234scalar }}",
+            commitments["issuer"], SUBJECT, commitments["dob"], commitments["nationality"], commitments["expiry"]
+        );
+
+        let commitment = hiding_invalidation_commitment::<N>(&credentials(), &salt).unwrap();
+        assert_eq!(commitment.to_string(), evaluate("hiding_invalidation", &[record])[0]);
+        assert_ne!(commitment, invalidation_commitment::<N>(&credentials(), &salt).unwrap());
+    }
+}
@@ -0,0 +1,256 @@
+use super::*;
+
+/// How a date is encoded into a `u32` credential attribute.
+///
+/// Every encoding preserves order, so comparing encoded values in Leo (`dob <= threshold`) gives
+/// the same answer as comparing the dates.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateEncoding {
+    /// The decimal digits `YYYYMMDD`, e.g. `20000101`.
+    #[default]
+    YyyyMmDd = 0,
+    /// Days since 1970-01-01.
+    DaysSinceEpoch = 1,
+    /// Seconds since 1970-01-01T00:00:00Z, at midnight of the date.
+    UnixSeconds = 2,
+}
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A date of the proleptic Gregorian calendar, between the years 1 and 9999.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Creates a date, checking it exists in the calendar.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, CustomError> {
+        if !(1..=9999).contains(&year) || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(anyhow!("{:04}-{:02}-{:02} is not a valid calendar date", year, month, day).into());
+        }
+        Ok(Self { year, month, day })
+    }
+
+    /// Returns the year.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Parses an ISO-8601 calendar date, in the extended (`2000-01-31`) or basic (`20000131`) format.
+    pub fn parse_iso(date: &str) -> Result<Self, CustomError> {
+        let date = date.trim();
+        let digits: String = match date.len() {
+            10 if date.as_bytes()[4] == b'-' && date.as_bytes()[7] == b'-' => [&date[..4], &date[5..7], &date[8..]].concat(),
+            8 => date.to_string(),
+            _ => return Err(anyhow!("'{}' is not an ISO-8601 date (YYYY-MM-DD)", date).into()),
+        };
+        if !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(anyhow!("'{}' is not an ISO-8601 date (YYYY-MM-DD)", date).into());
+        }
+        Self::new(digits[..4].parse().unwrap_or(0), digits[4..6].parse().unwrap_or(0), digits[6..].parse().unwrap_or(0))
+    }
+
+    /// Formats the date in the ISO-8601 extended format.
+    pub fn to_iso(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Returns the number of days since 1970-01-01, negative before it.
+    pub fn days_since_epoch(&self) -> i64 {
+        // Days from civil, counting in eras of 400 years that start on March 1st.
+        let year = self.year as i64 - (self.month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Returns the date `days` days after 1970-01-01.
+    pub fn from_days_since_epoch(days: i64) -> Result<Self, CustomError> {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+        let year = year_of_era + era * 400 + (month <= 2) as i64;
+        let year = u16::try_from(year).map_err(|_| anyhow!("Day {} is outside the supported years", days - 719_468))?;
+        Self::new(year, month as u8, day as u8)
+    }
+
+    /// Encodes the date into a `u32`.
+    pub fn encode(&self, encoding: DateEncoding) -> Result<u32, CustomError> {
+        let value = match encoding {
+            DateEncoding::YyyyMmDd => return Ok(self.year as u32 * 10_000 + self.month as u32 * 100 + self.day as u32),
+            DateEncoding::DaysSinceEpoch => self.days_since_epoch(),
+            DateEncoding::UnixSeconds => self.days_since_epoch() * SECONDS_PER_DAY,
+        };
+        u32::try_from(value)
+            .map_err(|_| anyhow!("{} cannot be encoded as {:?} in a u32", self.to_iso(), encoding).into())
+    }
+
+    /// Decodes a date from a `u32`. Unix seconds must fall on midnight.
+    pub fn decode(value: u32, encoding: DateEncoding) -> Result<Self, CustomError> {
+        match encoding {
+            DateEncoding::YyyyMmDd => {
+                let year = u16::try_from(value / 10_000).map_err(|_| anyhow!("{} is not a YYYYMMDD date", value))?;
+                Self::new(year, (value / 100 % 100) as u8, (value % 100) as u8)
+            }
+            DateEncoding::DaysSinceEpoch => Self::from_days_since_epoch(value as i64),
+            DateEncoding::UnixSeconds => {
+                if value as i64 % SECONDS_PER_DAY != 0 {
+                    return Err(anyhow!("{} is not midnight of a date", value).into());
+                }
+                Self::from_days_since_epoch(value as i64 / SECONDS_PER_DAY)
+            }
+        }
+    }
+
+    /// Returns the age in whole years, on the date `at`, of someone born on this date.
+    ///
+    /// Birthdays on February 29th are reached on March 1st in other years.
+    pub fn age_at(&self, at: &Date) -> Result<u32, CustomError> {
+        if at < self {
+            return Err(anyhow!("{} is before the date of birth {}", at.to_iso(), self.to_iso()).into());
+        }
+        let years = (at.year - self.year) as u32;
+        Ok(if (at.month, at.day) < (self.month, self.day) { years - 1 } else { years })
+    }
+
+    /// Returns the latest date of birth of someone at least `years` old on this date.
+    ///
+    /// A credential proves the age in Leo with `dob <= threshold` on the encoded values, which
+    /// agrees with `age_at` for every date of birth.
+    pub fn age_threshold(&self, years: u16) -> Result<Date, CustomError> {
+        let year = self.year.checked_sub(years).filter(|year| *year >= 1)
+            .ok_or_else(|| anyhow!("{} years before {} is outside the supported years", years, self.to_iso()))?;
+        Self::new(year, self.month, self.day.min(days_in_month(year, self.month)))
+    }
+}
+
+impl FromStr for Date {
+    type Err = CustomError;
+
+    fn from_str(date: &str) -> Result<Self, Self::Err> {
+        Self::parse_iso(date)
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_iso())
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Returns whether the encoded date `value` is before `other`, checking both are valid dates.
+/// Leo computes the same answer with `value < other`.
+pub fn is_encoded_date_before(value: u32, other: u32, encoding: DateEncoding) -> Result<bool, CustomError> {
+    Ok(Date::decode(value, encoding)? < Date::decode(other, encoding)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> Date {
+        Date::parse_iso(date).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_validate() {
+        assert_eq!(date("2000-01-31"), Date::new(2000, 1, 31).unwrap());
+        assert_eq!(date("20000131"), date("2000-01-31"));
+        assert_eq!(date("2024-02-29").to_iso(), "2024-02-29");
+
+        for invalid in ["2023-02-29", "1900-02-29", "2000-13-01", "2000-04-31", "0000-01-01", "2000-1-01", "2000/01/01", "+2000101", ""] {
+            assert!(Date::parse_iso(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_encodings_roundtrip() {
+        let dob = date("2000-01-01");
+        assert_eq!(dob.encode(DateEncoding::YyyyMmDd).unwrap(), 20000101);
+        assert_eq!(dob.encode(DateEncoding::DaysSinceEpoch).unwrap(), 10957);
+        assert_eq!(dob.encode(DateEncoding::UnixSeconds).unwrap(), 946684800);
+        assert_eq!(date("1970-01-01").days_since_epoch(), 0);
+        assert_eq!(date("1969-12-31").days_since_epoch(), -1);
+
+        for encoding in [DateEncoding::YyyyMmDd, DateEncoding::DaysSinceEpoch, DateEncoding::UnixSeconds] {
+            for iso in ["1970-01-01", "2000-02-29", "2024-12-31", "2100-03-01"] {
+                let value = date(iso).encode(encoding).unwrap();
+                assert_eq!(Date::decode(value, encoding).unwrap(), date(iso));
+            }
+        }
+
+        assert!(date("1969-12-31").encode(DateEncoding::DaysSinceEpoch).is_err());
+        assert!(date("2200-01-01").encode(DateEncoding::UnixSeconds).is_err());
+        assert!(Date::decode(946684801, DateEncoding::UnixSeconds).is_err());
+        assert!(Date::decode(20230229, DateEncoding::YyyyMmDd).is_err());
+    }
+
+    #[test]
+    fn test_ordering_matches_encoded_comparison() {
+        let dates = ["1970-01-01", "1999-12-31", "2000-01-01", "2000-02-29", "2000-03-01", "2024-12-31"];
+        for encoding in [DateEncoding::YyyyMmDd, DateEncoding::DaysSinceEpoch, DateEncoding::UnixSeconds] {
+            for a in dates {
+                for b in dates {
+                    let (x, y) = (date(a).encode(encoding).unwrap(), date(b).encode(encoding).unwrap());
+                    assert_eq!(is_encoded_date_before(x, y, encoding).unwrap(), date(a) < date(b));
+                    assert_eq!(x < y, date(a) < date(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_age_matches_threshold() {
+        assert_eq!(date("2000-06-15").age_at(&date("2018-06-14")).unwrap(), 17);
+        assert_eq!(date("2000-06-15").age_at(&date("2018-06-15")).unwrap(), 18);
+        assert_eq!(date("2004-02-29").age_at(&date("2022-02-28")).unwrap(), 17);
+        assert_eq!(date("2004-02-29").age_at(&date("2022-03-01")).unwrap(), 18);
+        assert!(date("2004-02-29").age_at(&date("2004-02-28")).is_err());
+
+        let dobs = ["2004-02-28", "2004-02-29", "2004-03-01", "2006-02-28", "2006-03-01"];
+        for at in ["2022-02-28", "2022-03-01", "2024-02-29", "2024-03-01"] {
+            let threshold = date(at).age_threshold(18).unwrap().encode(DateEncoding::YyyyMmDd).unwrap();
+            for dob in dobs {
+                let encoded = date(dob).encode(DateEncoding::YyyyMmDd).unwrap();
+                assert_eq!(encoded <= threshold, date(dob).age_at(&date(at)).unwrap() >= 18, "{} at {}", dob, at);
+            }
+        }
+    }
+}
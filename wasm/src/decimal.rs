@@ -0,0 +1,177 @@
+use super::*;
+
+/// The largest supported scale, as `10^38` is the largest power of ten in a `u128`.
+pub const MAX_DECIMAL_SCALE: u8 = 38;
+
+/// The integer type a decimal attribute is stored as.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecimalType {
+    U64 = 0,
+    I64 = 1,
+    U128 = 2,
+}
+
+impl DecimalType {
+    /// Returns the literal suffix of the type.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            DecimalType::U64 => "u64",
+            DecimalType::I64 => "i64",
+            DecimalType::U128 => "u128",
+        }
+    }
+
+    // Checks the value, given as a sign and a magnitude, fits in the type.
+    fn fits(&self, negative: bool, magnitude: u128) -> bool {
+        match self {
+            DecimalType::U64 => (!negative || magnitude == 0) && magnitude <= u64::MAX as u128,
+            DecimalType::I64 if negative => magnitude <= i64::MIN.unsigned_abs() as u128,
+            DecimalType::I64 => magnitude <= i64::MAX as u128,
+            DecimalType::U128 => !negative || magnitude == 0,
+        }
+    }
+}
+
+/// A fixed-point decimal attribute layout: decimals with `scale` fractional digits, stored as
+/// the integer `value * 10^scale`.
+///
+/// Decimals needing more fractional digits than the scale are rejected instead of rounded, so
+/// an encoded attribute always represents exactly the decimal it was given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecimalAttribute {
+    scale: u8,
+    integer_type: DecimalType,
+}
+
+impl DecimalAttribute {
+    /// Creates a layout with `scale` fractional digits, stored as `integer_type`.
+    pub fn new(scale: u8, integer_type: DecimalType) -> Result<Self, CustomError> {
+        if scale > MAX_DECIMAL_SCALE {
+            return Err(anyhow!("Decimal scale must be at most {}, got {}", MAX_DECIMAL_SCALE, scale).into());
+        }
+        Ok(Self { scale, integer_type })
+    }
+
+    /// Returns the number of fractional digits.
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Returns the integer type.
+    pub fn integer_type(&self) -> DecimalType {
+        self.integer_type
+    }
+
+    /// Encodes a decimal such as `-12.5` into its integer literal, e.g. `-1250i64` at scale 2.
+    pub fn encode(&self, decimal: &str) -> Result<String, CustomError> {
+        let invalid = || anyhow!("'{}' is not a decimal number", decimal);
+        let trimmed = decimal.trim();
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if integer.is_empty() && fraction.is_empty()
+            || !integer.bytes().chain(fraction.bytes()).all(|byte| byte.is_ascii_digit())
+        {
+            return Err(invalid().into());
+        }
+
+        let scale = self.scale as usize;
+        if fraction.len() > scale && fraction[scale..].bytes().any(|byte| byte != b'0') {
+            return Err(anyhow!("'{}' has more than {} fractional digits and would need rounding", decimal, scale).into());
+        }
+        let digits = format!("{}{:0<scale$}", integer, &fraction[..fraction.len().min(scale)], scale = scale);
+        let magnitude = digits
+            .bytes()
+            .try_fold(0u128, |acc, digit| acc.checked_mul(10)?.checked_add((digit - b'0') as u128))
+            .filter(|magnitude| self.integer_type.fits(negative, *magnitude))
+            .ok_or_else(|| anyhow!("'{}' does not fit in a {} at scale {}", decimal, self.integer_type.suffix(), scale))?;
+
+        let sign = if negative && magnitude != 0 { "-" } else { "" };
+        Ok(format!("{}{}{}", sign, magnitude, self.integer_type.suffix()))
+    }
+
+    /// Decodes an integer literal such as `-1250i64` into its decimal, e.g. `-12.50` at scale 2.
+    pub fn decode(&self, literal: &str) -> Result<String, CustomError> {
+        let value = literal.trim().strip_suffix(self.integer_type.suffix())
+            .ok_or_else(|| anyhow!("'{}' is not a {} literal", literal, self.integer_type.suffix()))?;
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, value),
+        };
+        let magnitude = digits.parse::<u128>().ok()
+            .filter(|_| !digits.starts_with('+'))
+            .filter(|magnitude| self.integer_type.fits(negative, *magnitude))
+            .ok_or_else(|| anyhow!("'{}' is not a valid {} literal", literal, self.integer_type.suffix()))?;
+
+        let digits = format!("{:0>width$}", magnitude, width = self.scale as usize + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        let sign = if negative && magnitude != 0 { "-" } else { "" };
+        Ok(match fraction.is_empty() {
+            true => format!("{}{}", sign, integer),
+            false => format!("{}{}.{}", sign, integer, fraction),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, SUBJECT};
+
+    #[test]
+    fn test_encode_and_decode() {
+        let percent = DecimalAttribute::new(2, DecimalType::U64).unwrap();
+        assert_eq!(percent.encode("12.5").unwrap(), "1250u64");
+        assert_eq!(percent.encode("0.07").unwrap(), "7u64");
+        assert_eq!(percent.encode(".5").unwrap(), "50u64");
+        assert_eq!(percent.encode("3.").unwrap(), "300u64");
+        assert_eq!(percent.encode("12.500").unwrap(), "1250u64");
+        assert_eq!(percent.decode("1250u64").unwrap(), "12.50");
+        assert_eq!(percent.decode("7u64").unwrap(), "0.07");
+
+        let balance = DecimalAttribute::new(4, DecimalType::I64).unwrap();
+        assert_eq!(balance.encode("-0.0001").unwrap(), "-1i64");
+        assert_eq!(balance.encode("-0").unwrap(), "0i64");
+        assert_eq!(balance.decode("-12345i64").unwrap(), "-1.2345");
+
+        let whole = DecimalAttribute::new(0, DecimalType::U128).unwrap();
+        assert_eq!(whole.encode("340282366920938463463374607431768211455").unwrap(), format!("{}u128", u128::MAX));
+        assert_eq!(whole.decode("42u128").unwrap(), "42");
+    }
+
+    #[test]
+    fn test_rounding_and_range_are_errors() {
+        let percent = DecimalAttribute::new(2, DecimalType::U64).unwrap();
+        assert!(percent.encode("12.345").is_err());
+        assert!(percent.encode("-1").is_err());
+        assert!(percent.encode("184467440737095516.16").is_err());
+        assert!(percent.encode("184467440737095516.15").is_ok());
+        for invalid in ["", ".", "1.2.3", "1e5", "12,5", "--1", "abc"] {
+            assert!(percent.encode(invalid).is_err(), "{}", invalid);
+        }
+
+        let balance = DecimalAttribute::new(0, DecimalType::I64).unwrap();
+        assert_eq!(balance.encode("-9223372036854775808").unwrap(), format!("{}i64", i64::MIN));
+        assert!(balance.encode("9223372036854775808").is_err());
+
+        assert!(percent.decode("1250u32").is_err());
+        assert!(percent.decode("-1u64").is_err());
+        assert!(DecimalAttribute::new(39, DecimalType::U128).is_err());
+    }
+
+    #[test]
+    fn test_encoded_literals_sign() {
+        let income = DecimalAttribute::new(2, DecimalType::U64).unwrap().encode("52000.75").unwrap();
+        let signer = PrivateKeySigner::<TestnetV0>::from_str(PRIVATE_KEY).unwrap();
+        let message = SignInboundMessage { data: serde_json::json!({
+            "issuer": signer.address().unwrap().to_string(),
+            "subject": SUBJECT,
+            "income": income,
+        }) };
+        assert!(sign_message_with_logger(&signer, message, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).is_ok());
+    }
+}
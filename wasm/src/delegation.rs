@@ -0,0 +1,333 @@
+use super::*;
+
+// Domain separator of the delegation certificate hash.
+const DELEGATION_DOMAIN: &str = "zPassDelegation";
+
+/// The credential attribute naming the schema a delegate issues the credential under.
+pub const SCHEMA_ATTRIBUTE: &str = "schema";
+
+/// Returns the schema attribute of the credential data.
+pub fn credential_schema(data: &JsonValue) -> Result<&str, CustomError> {
+    let schema = data.get(SCHEMA_ATTRIBUTE).ok_or_else(|| CustomError::MissingAttribute(SCHEMA_ATTRIBUTE.to_string()))?;
+    Ok(schema.as_str().ok_or_else(|| anyhow!("The '{}' attribute must be a string, got {}", SCHEMA_ATTRIBUTE, schema))?)
+}
+
+/// The statement a delegation certificate signs: `issuer` authorises `delegate` to issue
+/// credentials of the listed schemas between `not_before` and `not_after` (unix seconds, inclusive).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DelegationClaims<N: NetworkNative> {
+    pub network: u16,
+    pub issuer: Address<N>,
+    pub delegate: Address<N>,
+    pub schemas: Vec<String>,
+    pub not_before: u64,
+    pub not_after: u64,
+}
+
+impl<N: NetworkNative> DelegationClaims<N> {
+    // The signed message is the hash of the canonical JSON encoding of the claims.
+    fn message(&self) -> Result<Vec<Field<N>>, CustomError> {
+        let encoded = serde_json::to_vec(self)
+            .map_err(|e| anyhow!("Failed to serialize delegation claims: {}", e))?;
+        Ok(vec![hash_bytes_to_field::<N>(DELEGATION_DOMAIN, &encoded)?])
+    }
+
+    /// Returns whether the delegate may issue credentials of the schema.
+    pub fn allows(&self, schema: &str) -> bool {
+        self.schemas.iter().any(|allowed| allowed == schema)
+    }
+
+    /// Returns whether the time (unix seconds) is inside the validity window.
+    pub fn is_valid_at(&self, time: u64) -> bool {
+        self.not_before <= time && time <= self.not_after
+    }
+}
+
+/// Delegation claims signed by their issuer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DelegationCertificate<N: NetworkNative> {
+    #[serde(flatten)]
+    pub claims: DelegationClaims<N>,
+    pub signature: Signature<N>,
+}
+
+impl<N: NetworkNative> DelegationCertificate<N> {
+    /// Authorises `delegate` to issue credentials of the schemas within the validity window.
+    pub fn issue(signer: &dyn CredentialSigner<N>, delegate: Address<N>, schemas: Vec<String>, not_before: u64, not_after: u64) -> Result<Self, CustomError> {
+        if schemas.is_empty() {
+            return Err(anyhow!("A delegation must allow at least one schema").into());
+        }
+        if not_before > not_after {
+            return Err(anyhow!("Delegation validity window ends ({}) before it starts ({})", not_after, not_before).into());
+        }
+        let claims = DelegationClaims { network: N::ID, issuer: signer.address()?, delegate, schemas, not_before, not_after };
+        let signature = signer.sign(&claims.message()?)?;
+        Ok(Self { claims, signature })
+    }
+
+    /// Checks the certificate is signed by its issuer.
+    pub fn verify_signature(&self) -> Result<(), CustomError> {
+        if self.claims.network != N::ID {
+            return Err(anyhow!("Delegation belongs to network {}, expected {}", self.claims.network, N::ID).into());
+        }
+        if !verify_signature_with_address_and_message(&self.signature, &self.claims.issuer, &self.claims.message()?) {
+            return Err(anyhow!("Delegation to {} is not signed by {}", self.claims.delegate, self.claims.issuer).into());
+        }
+        Ok(())
+    }
+}
+
+impl<N: NetworkNative> FromStr for DelegationCertificate<N> {
+    type Err = CustomError;
+
+    /// Parses and verifies a certificate from its JSON encoding.
+    fn from_str(certificate: &str) -> Result<Self, Self::Err> {
+        let certificate: Self = serde_json::from_str(certificate)
+            .map_err(|e| anyhow!("Malformed delegation certificate: {}", e))?;
+        certificate.verify_signature()?;
+        Ok(certificate)
+    }
+}
+
+impl<N: NetworkNative> std::fmt::Display for DelegationCertificate<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+/// Checks that the chain, ordered from the root's certificate down, authorises `signer` to issue
+/// credentials of `schema` at `time` on behalf of `root`.
+///
+/// Every certificate must be signed by the delegate of the previous one (the root for the first),
+/// be valid at `time`, and allow `schema`.
+pub fn verify_delegation_chain<N: NetworkNative>(chain: &[DelegationCertificate<N>], root: &Address<N>, signer: &Address<N>, schema: &str, time: u64) -> Result<(), CustomError> {
+    if chain.is_empty() {
+        return Err(anyhow!("The delegation chain is empty").into());
+    }
+    let mut authority = *root;
+    for (depth, certificate) in chain.iter().enumerate() {
+        let claims = &certificate.claims;
+        if claims.issuer != authority {
+            return Err(anyhow!("Delegation {} is issued by {}, expected {}", depth, claims.issuer, authority).into());
+        }
+        certificate.verify_signature()?;
+        if !claims.is_valid_at(time) {
+            return Err(anyhow!("Delegation {} to {} is not valid at {}", depth, claims.delegate, time).into());
+        }
+        if !claims.allows(schema) {
+            return Err(anyhow!("Delegation {} to {} does not allow schema '{}'", depth, claims.delegate, schema).into());
+        }
+        authority = claims.delegate;
+    }
+    if authority != *signer {
+        return Err(anyhow!("The delegation chain ends at {}, not at the signer {}", authority, signer).into());
+    }
+    Ok(())
+}
+
+/// A credential signature made by a delegate, carrying the chain that authorises it.
+///
+/// The delegate signs the credential hash like any issuer, so the signature is also valid for the
+/// `issue` transitions. The schema is the credential's signed `schema` attribute, which
+/// `verify_attributes` checks against the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct DelegatedSignature<N: NetworkNative> {
+    pub hash: String,
+    pub issuer: Address<N>,
+    pub signature: Signature<N>,
+    pub chain: Vec<DelegationCertificate<N>>,
+}
+
+impl<N: NetworkNative> DelegatedSignature<N> {
+    /// Checks the signature over the hash and that the chain authorises its issuer for the schema
+    /// under `root` at `time`. The schema is not part of the signature: use `verify_attributes` to
+    /// check it is the one the credential was signed with.
+    pub fn verify(&self, root: &Address<N>, schema: &str, time: u64) -> Result<(), CustomError> {
+        if !verify_hash(&self.signature, &self.issuer, &self.hash)? {
+            return Err(anyhow!("Credential signature does not match the issuer {} and hash {}", self.issuer, self.hash).into());
+        }
+        verify_delegation_chain(&self.chain, root, &self.issuer, schema, time)
+    }
+
+    /// Verifies the signature like `verify`, for the schema attribute of the message, after checking
+    /// that its hash is the hash of the attributes in the message and that the attributes are inside
+    /// their validity window at the date of `time`. Their validity attributes must be `YYYYMMDD` dates.
+    pub fn verify_attributes(&self, message: SignInboundMessage, root: &Address<N>, time: u64, hash: HashAlgorithm, output: HashOutputType, logger: &dyn Logger) -> Result<(), CustomError> {
+        Validity::check_data(&message.data, Some(ReferenceTime::date_of_unix(time)?))?;
+        let schema = credential_schema(&message.data)?.to_string();
+        let hash = hash_credential::<N>(message, hash, output, logger)?;
+        if hash != self.hash {
+            return Err(anyhow!("The credential hash {} is not the hash {} of the attributes", self.hash, hash).into());
+        }
+        self.verify(root, &schema, time)
+    }
+}
+
+impl<N: NetworkNative> FromStr for DelegatedSignature<N> {
+    type Err = CustomError;
+
+    /// Parses a delegated signature from its JSON encoding. It is checked by `verify`.
+    fn from_str(signature: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(signature).map_err(|e| anyhow!("Malformed delegated signature: {}", e))?)
+    }
+}
+
+impl<N: NetworkNative> std::fmt::Display for DelegatedSignature<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+/// How a delegate signs a credential: the hashing, the chain that authorises it at `time` (unix
+/// seconds) and the checks run on the credential.
+#[derive(Debug, Clone)]
+pub struct DelegationOptions<N: NetworkNative> {
+    pub hash: HashAlgorithm,
+    pub output: HashOutputType,
+    pub chain: Vec<DelegationCertificate<N>>,
+    pub time: u64,
+    pub checks: SignChecks,
+}
+
+/// Signs the message on behalf of the root of the chain, attaching the chain.
+///
+/// The credential is checked, hashed and signed like `sign_message_with_checks`, so the signature
+/// can be used with the `issue` transitions. The credential must name its schema in the `schema`
+/// attribute, and the chain must end at the signer and be valid for that schema at the given time,
+/// so a delegate cannot produce credentials it is not authorised for.
+pub fn sign_message_with_delegation<N: NetworkNative>(
+    signer: &dyn CredentialSigner<N>,
+    message: SignInboundMessage,
+    options: DelegationOptions<N>,
+    logger: &dyn Logger
+) -> Result<DelegatedSignature<N>, CustomError> {
+    let DelegationOptions { hash, output, chain, time, checks } = options;
+    let issuer = signer.address()?;
+    let root = chain.first().ok_or_else(|| anyhow!("The delegation chain is empty"))?.claims.issuer;
+    verify_delegation_chain(&chain, &root, &issuer, credential_schema(&message.data)?, time)?;
+
+    let (signature, hash) = sign_message_with_checks(signer, message, hash, output, &checks, logger)?;
+    let signature = Signature::<N>::from_str(&signature)
+        .map_err(|e| anyhow!("Failed to parse signature: {}", e))?;
+    Ok(DelegatedSignature { hash, issuer, signature, chain })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::SUBJECT;
+    use serde_json::json;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    const SCHEMA: &str = "national_id";
+
+    fn signer() -> PrivateKeySigner<N> {
+        IssuerAccount::<N>::generate().unwrap().into_signer().unwrap()
+    }
+
+    fn delegate(from: &PrivateKeySigner<N>, to: &PrivateKeySigner<N>, schemas: &[&str], not_after: u64) -> DelegationCertificate<N> {
+        let schemas = schemas.iter().map(|schema| schema.to_string()).collect();
+        DelegationCertificate::issue(from, to.address().unwrap(), schemas, 100, not_after).unwrap()
+    }
+
+    fn options(chain: &[DelegationCertificate<N>], time: u64) -> DelegationOptions<N> {
+        DelegationOptions { hash: HashAlgorithm::POSEIDON2, output: HashOutputType::Field, chain: chain.to_vec(), time, checks: SignChecks::default().at(ReferenceTime::date(20240101).unwrap()) }
+    }
+
+    fn message(issuer: &PrivateKeySigner<N>, schema: &str) -> SignInboundMessage {
+        SignInboundMessage {
+            data: json!({
+                "issuer": issuer.address().unwrap().to_string(),
+                "subject": SUBJECT,
+                "dob": "20000101u32",
+                "schema": schema,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_certificate_roundtrip() {
+        let (root, office) = (signer(), signer());
+        let certificate = delegate(&root, &office, &[SCHEMA], 200);
+        assert_eq!(DelegationCertificate::<N>::from_str(&certificate.to_string()).unwrap(), certificate);
+
+        let mut tampered: JsonValue = serde_json::from_str(&certificate.to_string()).unwrap();
+        tampered["not_after"] = JsonValue::from(u64::MAX);
+        assert!(DelegationCertificate::<N>::from_str(&tampered.to_string()).is_err());
+
+        assert!(DelegationCertificate::issue(&root, office.address().unwrap(), vec![], 100, 200).is_err());
+        assert!(DelegationCertificate::issue(&root, office.address().unwrap(), vec![SCHEMA.to_string()], 200, 100).is_err());
+    }
+
+    #[test]
+    fn test_delegated_signature_through_chain() {
+        let (root, region, office) = (signer(), signer(), signer());
+        let chain = vec![delegate(&root, &region, &[SCHEMA, "driving_licence"], 300), delegate(&region, &office, &[SCHEMA], 200)];
+
+        let signed = sign_message_with_delegation(&office, message(&office, SCHEMA), options(&chain, 150), &StdoutLogger).unwrap();
+        let parsed = DelegatedSignature::<N>::from_str(&signed.to_string()).unwrap();
+        let root_address = root.address().unwrap();
+        assert!(parsed.verify(&root_address, SCHEMA, 150).is_ok());
+
+        // Expired, out of scope, or rooted elsewhere.
+        assert!(parsed.verify(&root_address, SCHEMA, 250).is_err());
+        assert!(parsed.verify(&root_address, "driving_licence", 150).is_err());
+        assert!(parsed.verify(&region.address().unwrap(), SCHEMA, 150).is_err());
+        assert!(sign_message_with_delegation(&office, message(&office, SCHEMA), options(&chain, 250), &StdoutLogger).is_err());
+        assert!(sign_message_with_delegation(&office, message(&office, "driving_licence"), options(&chain, 150), &StdoutLogger).is_err());
+
+        // The chain must be unbroken and end at the signer.
+        assert!(verify_delegation_chain(&chain[1..], &root_address, &office.address().unwrap(), SCHEMA, 150).is_err());
+        assert!(sign_message_with_delegation(&region, message(&region, SCHEMA), options(&chain, 150), &StdoutLogger).is_err());
+
+        // The credential must name its schema.
+        let mut unnamed = message(&office, SCHEMA);
+        unnamed.data.as_object_mut().unwrap().remove(SCHEMA_ATTRIBUTE);
+        assert!(matches!(sign_message_with_delegation(&office, unnamed, options(&chain, 150), &StdoutLogger), Err(CustomError::MissingAttribute(_))));
+    }
+
+    #[test]
+    fn test_schema_is_a_signed_attribute() {
+        let (root, office) = (signer(), signer());
+        let chain = vec![delegate(&root, &office, &[SCHEMA, "driving_licence"], 200)];
+        let signed = sign_message_with_delegation(&office, message(&office, SCHEMA), options(&chain, 150), &StdoutLogger).unwrap();
+        let root_address = root.address().unwrap();
+        let verify = |message: SignInboundMessage| signed.verify_attributes(message, &root_address, 150, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger);
+
+        // Relabelling the credential with another schema the chain allows changes its hash.
+        assert!(verify(message(&office, SCHEMA)).is_ok());
+        assert!(verify(message(&office, "driving_licence")).is_err());
+
+        // The signature is a plain credential signature, as the `issue` transitions check it.
+        let (verified, hash) = verify_credential_with_logger(message(&office, SCHEMA), &signed.signature, &office.address().unwrap(), HashAlgorithm::POSEIDON2, HashOutputType::Field, None, &StdoutLogger).unwrap();
+        assert!(verified);
+        assert_eq!(hash, signed.hash);
+    }
+
+    #[test]
+    fn test_validity_window() {
+        let (root, office) = (signer(), signer());
+        // 2024-01-01 and 2100-01-01 in unix seconds.
+        let (start, end) = (1_704_067_200, 4_102_444_800);
+        let chain = vec![delegate(&root, &office, &[SCHEMA], end + 86_400)];
+        let mut message = message(&office, SCHEMA);
+        message.data["expiry"] = json!("20991231u32");
+        let signed = sign_message_with_delegation(&office, message.clone(), options(&chain, start), &StdoutLogger).unwrap();
+        let root_address = root.address().unwrap();
+
+        let verify = |message: SignInboundMessage, time: u64| signed.verify_attributes(message, &root_address, time, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger);
+        assert!(verify(message.clone(), start).is_ok());
+        assert!(matches!(verify(message.clone(), end), Err(CustomError::Expired { expiry: 20991231, time: 21000101 })));
+
+        // The chain alone is still valid then, so only the attributes catch the expired credential.
+        assert!(signed.verify(&root_address, SCHEMA, end).is_ok());
+        let mut other = message;
+        other.data["dob"] = json!("20000102u32");
+        assert!(verify(other, start).is_err());
+    }
+}
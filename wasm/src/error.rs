@@ -4,4 +4,18 @@ use thiserror::Error;
 pub enum CustomError {
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
+    #[error("Credential data must be a JSON object")]
+    InvalidCredentialData,
+    #[error("Credential attribute '{0}' is required but missing")]
+    MissingAttribute(String),
+    #[error("Issuer attribute {found} does not match the signing key address {expected}")]
+    IssuerMismatch { expected: String, found: String },
+    #[error("Subject attribute {0} is not a valid address")]
+    InvalidSubject(String),
+    #[error("Invalid credential validity: {0}")]
+    InvalidValidity(String),
+    #[error("The credential is not valid before {not_before}, reference time is {time}")]
+    NotYetValid { not_before: u32, time: u32 },
+    #[error("The credential expired at {expiry}, reference time is {time}")]
+    Expired { expiry: u32, time: u32 },
 }
@@ -0,0 +1,196 @@
+use super::*;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// A hashing scheme mapping a credential value to the field message that is signed.
+///
+/// Implementations must be deterministic, since verifiers (and Leo programs) recompute the same
+/// hash from the credential attributes.
+pub trait CredentialHasher<N: NetworkNative>: Send + Sync {
+    /// Returns the name the hasher is registered and selected under.
+    fn name(&self) -> &str;
+
+    /// Hashes the credential value into the field that gets signed.
+    fn hash(&self, value: &Value<N>) -> Result<Field<N>, anyhow::Error>;
+}
+
+/// A hasher backed by one of the built-in `HashAlgorithm`s, producing a field like `hash_to_field`.
+#[derive(Debug, Clone, Copy)]
+pub struct AlgorithmHasher {
+    algorithm: HashAlgorithm,
+}
+
+impl AlgorithmHasher {
+    /// Creates a hasher for the given algorithm.
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        Self { algorithm }
+    }
+
+    /// Returns the registry name of the given algorithm.
+    pub fn name_of(algorithm: HashAlgorithm) -> &'static str {
+        match algorithm {
+            HashAlgorithm::POSEIDON2 => "poseidon2",
+            HashAlgorithm::BHP1024 => "bhp1024",
+            HashAlgorithm::SHA3_256 => "sha3_256",
+            HashAlgorithm::KECCAK256 => "keccak256",
+        }
+    }
+}
+
+impl<N: NetworkNative> CredentialHasher<N> for AlgorithmHasher {
+    fn name(&self) -> &str {
+        Self::name_of(self.algorithm)
+    }
+
+    fn hash(&self, value: &Value<N>) -> Result<Field<N>, anyhow::Error> {
+        let hash = create_hash(value.clone(), self.algorithm, HashOutputType::Field)?;
+        Field::<N>::from_str(&hash)
+    }
+}
+
+/// A set of credential hashers addressable by name.
+pub struct HasherRegistry<N: NetworkNative> {
+    hashers: IndexMap<String, Arc<dyn CredentialHasher<N>>>,
+}
+
+impl<N: NetworkNative> HasherRegistry<N> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self { hashers: IndexMap::new() }
+    }
+
+    /// Creates a registry holding a hasher for every built-in `HashAlgorithm`.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        for algorithm in [HashAlgorithm::POSEIDON2, HashAlgorithm::BHP1024, HashAlgorithm::SHA3_256, HashAlgorithm::KECCAK256] {
+            registry.hashers.insert(AlgorithmHasher::name_of(algorithm).to_string(), Arc::new(AlgorithmHasher::new(algorithm)));
+        }
+        registry
+    }
+
+    /// Registers a hasher under its name. Fails if the name is already taken.
+    pub fn register(&mut self, hasher: Arc<dyn CredentialHasher<N>>) -> Result<(), CustomError> {
+        let name = hasher.name().to_string();
+        if self.hashers.contains_key(&name) {
+            return Err(anyhow!("A hasher named '{}' is already registered", name).into());
+        }
+        self.hashers.insert(name, hasher);
+        Ok(())
+    }
+
+    /// Removes the hasher with the given name, returning it if it was registered.
+    pub fn unregister(&mut self, name: &str) -> Option<Arc<dyn CredentialHasher<N>>> {
+        self.hashers.shift_remove(name)
+    }
+
+    /// Returns the hasher with the given name.
+    pub fn get(&self, name: &str) -> Result<Arc<dyn CredentialHasher<N>>, CustomError> {
+        self.hashers
+            .get(name)
+            .cloned()
+            .ok_or_else(|| anyhow!("No hasher named '{}' is registered", name).into())
+    }
+
+    /// Returns the names of the registered hashers in registration order.
+    pub fn names(&self) -> Vec<String> {
+        self.hashers.keys().cloned().collect()
+    }
+}
+
+impl<N: NetworkNative> Default for HasherRegistry<N> {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+// Process-wide registries, one per network, keyed by the network ID.
+type Registries = HashMap<u16, Box<dyn Any + Send + Sync>>;
+
+fn global_registries() -> &'static RwLock<Registries> {
+    static REGISTRIES: OnceLock<RwLock<Registries>> = OnceLock::new();
+    REGISTRIES.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Runs `f` against the global registry of network `N`, creating it with the defaults on first use.
+pub fn with_global_registry<N: NetworkNative, T>(f: impl FnOnce(&mut HasherRegistry<N>) -> T) -> T {
+    let mut registries = global_registries().write().unwrap_or_else(|e| e.into_inner());
+    let registry = registries
+        .entry(N::ID)
+        .or_insert_with(|| Box::new(HasherRegistry::<N>::with_defaults()))
+        .downcast_mut::<HasherRegistry<N>>()
+        .expect("Hasher registry has the wrong network type");
+    f(registry)
+}
+
+/// Registers a hasher in the global registry of network `N`, making it selectable by name.
+pub fn register_hasher<N: NetworkNative>(hasher: Arc<dyn CredentialHasher<N>>) -> Result<(), CustomError> {
+    with_global_registry::<N, _>(|registry| registry.register(hasher))
+}
+
+/// Returns the hasher registered under `name` in the global registry of network `N`.
+pub fn get_hasher<N: NetworkNative>(name: &str) -> Result<Arc<dyn CredentialHasher<N>>, CustomError> {
+    with_global_registry::<N, _>(|registry| registry.get(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::string_to_value;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    // Hashes the Poseidon2 hash of the value a second time, as a stand-in for a partner scheme.
+    struct DoublePoseidon2;
+
+    impl CredentialHasher<N> for DoublePoseidon2 {
+        fn name(&self) -> &str {
+            "double_poseidon2"
+        }
+
+        fn hash(&self, value: &Value<N>) -> Result<Field<N>, anyhow::Error> {
+            let first = N::hash_psd2(&value.to_fields()?)?;
+            N::hash_psd2(&[first])
+        }
+    }
+
+    #[test]
+    fn test_default_hashers_match_create_hash() {
+        let registry = HasherRegistry::<N>::with_defaults();
+        let value = string_to_value::<N>("123field");
+
+        let hasher = registry.get("poseidon2").unwrap();
+        let expected = create_hash(value.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap();
+        assert_eq!(hasher.hash(&value).unwrap().to_string(), expected);
+        assert_eq!(registry.names(), vec!["poseidon2", "bhp1024", "sha3_256", "keccak256"]);
+    }
+
+    #[test]
+    fn test_register_custom_hasher() {
+        let mut registry = HasherRegistry::<N>::new();
+        registry.register(Arc::new(DoublePoseidon2)).unwrap();
+
+        assert!(registry.register(Arc::new(DoublePoseidon2)).is_err());
+        assert!(registry.get("poseidon2").is_err());
+
+        let value = string_to_value::<N>("123field");
+        let hash = registry.get("double_poseidon2").unwrap().hash(&value).unwrap();
+        assert_ne!(hash.to_string(), create_hash(value, HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap());
+
+        assert!(registry.unregister("double_poseidon2").is_some());
+        assert!(registry.get("double_poseidon2").is_err());
+    }
+
+    #[test]
+    fn test_registry_with_defaults() {
+        let mut registry = HasherRegistry::<N>::default();
+        registry.register(Arc::new(DoublePoseidon2)).unwrap();
+        assert!(registry.get("double_poseidon2").is_ok());
+        assert!(registry.get("poseidon2").is_ok());
+
+        // Local registries leave the global ones untouched.
+        assert!(get_hasher::<N>("poseidon2").is_ok());
+        assert!(get_hasher::<N>("double_poseidon2").is_err());
+    }
+}
@@ -2,6 +2,7 @@ use super::*;
 use hex::encode;
 use wasm_bindgen::prelude::JsValue;
 use web_sys::console;
+use snarkvm_console::prelude::{FromBits, SizeInDataBits};
 use crate::{Field, NetworkNative};
 
 pub trait Logger {
@@ -25,101 +26,107 @@ impl Logger for StdoutLogger {
 }
 
 /// Arbitrary message to StructType conversion
-pub fn convert_data_to_struct<N: NetworkNative>(data: JsonValue, logger: &dyn Logger) -> IndexMap<String, Plaintext<N>> {
+pub fn convert_data_to_struct<N: NetworkNative>(data: JsonValue, logger: &dyn Logger) -> Result<IndexMap<String, Plaintext<N>>, anyhow::Error> {
     let mut members: IndexMap<String, Plaintext<N>> = IndexMap::new();
 
-    for (key, value) in data.as_object().unwrap().clone().into_iter() {
+    let data = data.as_object().ok_or_else(|| anyhow!("Credential data must be a JSON object"))?;
+    // The snarkvm-synthesizer-program dependency enables serde_json's `preserve_order` feature for
+    // the whole crate graph, so JSON objects keep their insertion order instead of sorting their keys.
+    // Sorting restores the key order the credential hashes have always been computed in.
+    let mut attributes: Vec<(String, JsonValue)> = data.clone().into_iter().collect();
+    attributes.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (key, value) in attributes {
         match value {
             JsonValue::String(s) => {
                 let plaintext = match s {
                     s if s.starts_with("aleo1") => {
                         let address = Address::<N>::from_str(&s)
-                            .unwrap_or_else(|e| panic!("Failed to parse Aleo address: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse Aleo address: {}", e))?;
                         Plaintext::from(Literal::Address(address))
                     },
                     s if s.ends_with("field") => {
                         let num_str = s.trim_end_matches("field");
                         let field = string_to_field::<N>(Some(num_str.to_string()))
-                            .unwrap_or_else(|e| panic!("Failed to parse field: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse field: {}", e))?;
                         Plaintext::from(Literal::Field(field))
                     },
                     s if s.ends_with("u8") => {
                         let num_str = s.trim_end_matches("u8");
                         let number = num_str.parse::<u8>()
-                            .unwrap_or_else(|e| panic!("Failed to parse u8: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse u8: {}", e))?;
                         Plaintext::from(Literal::U8(U8::<N>::new(number)))
                     },
                     s if s.ends_with("u16") => {
                         let num_str = s.trim_end_matches("u16");
                         let number = num_str.parse::<u16>()
-                            .unwrap_or_else(|e| panic!("Failed to parse u16: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse u16: {}", e))?;
                         Plaintext::from(Literal::U16(U16::<N>::new(number)))
                     },
                     s if s.ends_with("u32") => {
                         let num_str = s.trim_end_matches("u32");
                         let number = num_str.parse::<u32>()
-                            .unwrap_or_else(|e| panic!("Failed to parse u32: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse u32: {}", e))?;
                         Plaintext::from(Literal::U32(U32::<N>::new(number)))
                     },
                     s if s.ends_with("u64") => {
                         let num_str = s.trim_end_matches("u64");
                         let number = num_str.parse::<u64>()
-                            .unwrap_or_else(|e| panic!("Failed to parse u64: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse u64: {}", e))?;
                         Plaintext::from(Literal::U64(U64::<N>::new(number)))
                     },
                     s if s.ends_with("u128") => {
                         let num_str = s.trim_end_matches("u128");
                         let number = num_str.parse::<u128>()
-                            .unwrap_or_else(|e| panic!("Failed to parse u128: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse u128: {}", e))?;
                         Plaintext::from(Literal::U128(U128::<N>::new(number)))
                     },
                     s if s.ends_with("i8") => {
                         let num_str = s.trim_end_matches("i8");
                         let number = num_str.parse::<i8>()
-                            .unwrap_or_else(|e| panic!("Failed to parse i8: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse i8: {}", e))?;
                         Plaintext::from(Literal::I8(I8::<N>::new(number)))
                     },
                     s if s.ends_with("i16") => {
                         let num_str = s.trim_end_matches("i16");
                         let number = num_str.parse::<i16>()
-                            .unwrap_or_else(|e| panic!("Failed to parse i16: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse i16: {}", e))?;
                         Plaintext::from(Literal::I16(I16::<N>::new(number)))
                     },
                     s if s.ends_with("i32") => {
                         let num_str = s.trim_end_matches("i32");
                         let number = num_str.parse::<i32>()
-                            .unwrap_or_else(|e| panic!("Failed to parse i32: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse i32: {}", e))?;
                         Plaintext::from(Literal::I32(I32::<N>::new(number)))
                     },
                     s if s.ends_with("i64") => {
                         let num_str = s.trim_end_matches("i64");
                         let number = num_str.parse::<i64>()
-                            .unwrap_or_else(|e| panic!("Failed to parse i64: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse i64: {}", e))?;
                         Plaintext::from(Literal::I64(I64::<N>::new(number)))
                     },
                     s if s.ends_with("i128") => {
                         let num_str = s.trim_end_matches("i128");
                         let number = num_str.parse::<i128>()
-                            .unwrap_or_else(|e| panic!("Failed to parse i128: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse i128: {}", e))?;
                         Plaintext::from(Literal::I128(I128::<N>::new(number)))
                     },
                     s if s == "true" || s == "false" => {
                         let (_, boolean) = Boolean::<N>::parse(&s)
-                            .unwrap_or_else(|e| panic!("Failed to parse boolean: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse boolean: {}", e))?;
                         Plaintext::from(Literal::Boolean(boolean))
                     },
                     s if s.ends_with("group") => {
                         let (_, group) = Group::<N>::parse(&s)
-                            .unwrap_or_else(|e| panic!("Failed to parse group: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse group: {}", e))?;
                         Plaintext::from(Literal::Group(group))
                     },
                     s if s.ends_with("scalar") => {
                         let (_, scalar) = Scalar::<N>::parse(&s)
-                            .unwrap_or_else(|e| panic!("Failed to parse scalar: {}", e));
+                            .map_err(|e| anyhow!("Failed to parse scalar: {}", e))?;
                         Plaintext::from(Literal::Scalar(scalar))
                     },
                     s => {
-                        let field = string_to_field(Some(s)).unwrap();
+                        let field = string_to_field(Some(s))?;
                         Plaintext::from(Literal::Field(field))
                     }
                 };
@@ -130,7 +137,7 @@ pub fn convert_data_to_struct<N: NetworkNative>(data: JsonValue, logger: &dyn Lo
             }
         }
     }
-    members
+    Ok(members)
 }
 
 pub fn string_to_field<N: NetworkNative>(input_str: Option<String>) -> Result<Field<N>, anyhow::Error> {
@@ -172,55 +179,60 @@ pub(crate) fn generate_message_with_addresses_and_fields<N: NetworkNative>(paylo
     Ok(Value::Plaintext(Plaintext::Struct(map, Default::default())))
 }
 
-pub(crate) fn create_hash<N: NetworkNative>(value: Value<N>, algorithm: HashAlgorithm) -> Result<String, anyhow::Error> {
+/// Hashes the value with the given algorithm into the requested output type.
+///
+/// Mirrors the snarkVM `hash` instruction behind Leo's `X::hash_to_T(value)`: BHP-based hashes,
+/// SHA3 and Keccak are computed as a group element, Poseidon hashes are computed as a field unless
+/// a group or address is requested, and the result is then lossily cast to the output type.
+pub(crate) fn create_hash<N: NetworkNative>(value: Value<N>, algorithm: HashAlgorithm, output: HashOutputType) -> Result<String, anyhow::Error> {
+    let is_group_output = matches!(output, HashOutputType::Group | HashOutputType::Address);
     let hash = match algorithm  {
         HashAlgorithm::POSEIDON2 => {
             let message = value.to_fields()
                 .map_err(|e| anyhow!("Failed value to Fields conversion: {}", e))?;
-            let hash = N::hash_psd2(message.as_slice())
-                .map_err(|e| anyhow!("Failed hash_psd2 conversion: {}", e))?;
-            hash.to_string()
+            if is_group_output {
+                let group = N::hash_to_group_psd2(message.as_slice())
+                    .map_err(|e| anyhow!("Failed hash_to_group_psd2 conversion: {}", e))?;
+                Literal::Group(group)
+            } else {
+                let field = N::hash_psd2(message.as_slice())
+                    .map_err(|e| anyhow!("Failed hash_psd2 conversion: {}", e))?;
+                Literal::Field(field)
+            }
         }
         HashAlgorithm::BHP1024 => {
             let message = value.to_bits_le();
-            let hash = N::hash_bhp1024(message.as_slice())
-                .map_err(|e| anyhow!("Failed hash_bhp1024 conversion: {}", e))?;
-            hash.to_string()
+            let group = N::hash_to_group_bhp1024(message.as_slice())
+                .map_err(|e| anyhow!("Failed hash_to_group_bhp1024 conversion: {}", e))?;
+            Literal::Group(group)
         }
         HashAlgorithm::SHA3_256 => {
             let message = value.to_bits_le();
             let sha_bit_vec = N::hash_sha3_256(message.as_slice())
                 .map_err(|e| anyhow!("Failed hash_sha3_256 conversion: {}", e))?;
-            let bhp_group = N::hash_to_group_bhp256(sha_bit_vec.as_slice())
+            let group = N::hash_to_group_bhp256(sha_bit_vec.as_slice())
                 .map_err(|e| anyhow!("Failed hash_to_group_bhp256 conversion: {}", e))?;
-            let literal_group_from_bhp = Literal::Group(bhp_group);
-            let casted_to_field = literal_group_from_bhp
-                .cast_lossy(snarkvm_console::program::LiteralType::Field)
-                .map_err(|e| anyhow!("Failed cast_lossy conversion: {}", e))?;
-
-            casted_to_field.to_string()
+            Literal::Group(group)
         }
         HashAlgorithm::KECCAK256 => {
             let message = value.to_bits_le();
             let keccak_bit_vec = N::hash_keccak256(message.as_slice())
                 .map_err(|e| anyhow!("Failed hash_keccak256 conversion: {}", e))?;
-            let bhp_group = N::hash_to_group_bhp256(keccak_bit_vec.as_slice())
+            let group = N::hash_to_group_bhp256(keccak_bit_vec.as_slice())
                 .map_err(|e| anyhow!("Failed hash_to_group_bhp256 conversion: {}", e))?;
-            let literal_group_from_bhp = Literal::Group(bhp_group);
-            let casted_to_field = literal_group_from_bhp
-                .cast_lossy(snarkvm_console::program::LiteralType::Field)
-                .map_err(|e| anyhow!("Failed cast_lossy conversion: {}", e))?;
-
-            casted_to_field.to_string()
+            Literal::Group(group)
         }
     };
-    Ok(hash)
+    let casted = hash
+        .cast_lossy(output.literal_type())
+        .map_err(|e| anyhow!("Failed cast_lossy conversion: {}", e))?;
+    Ok(casted.to_string())
 }
 
-pub(crate) fn sign_message_with_private_key<N: NetworkNative>(
+pub(crate) fn sign_message_with_private_key<N: NetworkNative, R: rand::Rng + rand::CryptoRng>(
     private_key: &PrivateKey<N>,
     message: &[Field<N>],
-    rng: &mut TestRng
+    rng: &mut R
 ) -> Result<(Signature<N>, Scalar<N>), anyhow::Error> {
     match Signature::<N>::sign(private_key, message, rng) {
         Ok(signature) => {
@@ -249,6 +261,20 @@ pub(crate) fn string_to_value_fields<N: NetworkNative>(s: &str) -> Vec<Field<N>>
     value.to_fields().expect("Can't convert value to fields")
 }
 
+/// Hashes the bytes, packed little-endian into fields, with Poseidon2 under a domain separator.
+pub(crate) fn hash_bytes_to_field<N: NetworkNative>(domain: &str, bytes: &[u8]) -> Result<Field<N>, anyhow::Error> {
+    // The bits are wiped once hashed, as the bytes may be a secret seed.
+    let bits: zeroize::Zeroizing<Vec<bool>> = zeroize::Zeroizing::new(
+        bytes.iter().flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1)).collect()
+    );
+    let mut inputs = vec![Field::<N>::new_domain_separator(domain)];
+    for chunk in bits.chunks(Field::<N>::size_in_data_bits()) {
+        inputs.push(Field::<N>::from_bits_le(chunk)?);
+    }
+    // The length keeps trailing zero bytes significant.
+    inputs.push(Field::<N>::from_u64(bytes.len() as u64));
+    N::hash_psd2(&inputs)
+}
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,278 @@
+use super::*;
+
+// Domain separator of the key endorsement hash.
+const ENDORSEMENT_DOMAIN: &str = "zPassKeyRotation";
+
+/// One issuer key in a key history, active from `activated_at` until `retired_at` (exclusive), or
+/// indefinitely while it is the current key. Times are in the time basis of the history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct KeyEntry<N: NetworkNative> {
+    pub address: Address<N>,
+    pub activated_at: u32,
+    pub retired_at: Option<u32>,
+    /// The previous key's signature over this entry, or the key's own signature for the root key.
+    pub endorsement: Signature<N>,
+}
+
+impl<N: NetworkNative> KeyEntry<N> {
+    /// Returns whether the key was active at the time.
+    pub fn is_active_at(&self, time: u32) -> bool {
+        self.activated_at <= time && self.retired_at.is_none_or(|retired_at| time < retired_at)
+    }
+}
+
+// The message the previous key signs to endorse the next one, retiring itself when it activates.
+// The root key endorses itself, which fixes its activation time.
+fn endorsement_message<N: NetworkNative>(basis: TimeBasis, previous: &Address<N>, next: &Address<N>, activated_at: u32) -> Result<Vec<Field<N>>, CustomError> {
+    let preimage = format!("{}:{:?}:{}:{}:{}", N::ID, basis, previous, next, activated_at);
+    Ok(vec![hash_bytes_to_field::<N>(ENDORSEMENT_DOMAIN, preimage.as_bytes())?])
+}
+
+/// The history of an issuer's keys, each one endorsed by its predecessor.
+///
+/// Verifiers pin the first (root) address; every later key is trusted through the chain of
+/// endorsements, so a credential stays verifiable under the key that was active when it was issued.
+/// Activation times use the same time basis as the `issued_at` attribute of the credentials.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct KeyHistory<N: NetworkNative> {
+    network: u16,
+    basis: TimeBasis,
+    keys: Vec<KeyEntry<N>>,
+}
+
+impl<N: NetworkNative> KeyHistory<N> {
+    /// Starts a history with the signer's key, active from `activated_at`, which also sets the time
+    /// basis of the history. The key signs its own entry.
+    pub fn new(signer: &dyn CredentialSigner<N>, activated_at: ReferenceTime) -> Result<Self, CustomError> {
+        let address = signer.address()?;
+        let endorsement = signer.sign(&endorsement_message(activated_at.basis, &address, &address, activated_at.value)?)?;
+        let root = KeyEntry { address, activated_at: activated_at.value, retired_at: None, endorsement };
+        Ok(Self { network: N::ID, basis: activated_at.basis, keys: vec![root] })
+    }
+
+    /// Returns how the activation times are encoded.
+    pub fn basis(&self) -> TimeBasis {
+        self.basis
+    }
+
+    /// Returns the keys, oldest first.
+    pub fn keys(&self) -> &[KeyEntry<N>] {
+        &self.keys
+    }
+
+    /// Returns the current key.
+    pub fn current(&self) -> &KeyEntry<N> {
+        // A history always holds at least its root key.
+        self.keys.last().expect("key history is empty")
+    }
+
+    /// Returns the key that was active at the time, if any.
+    pub fn key_at(&self, time: u32) -> Option<&KeyEntry<N>> {
+        self.keys.iter().find(|key| key.is_active_at(time))
+    }
+
+    /// Retires the current key at `activated_at` in favour of `next`, endorsed by the current key.
+    pub fn rotate(&mut self, current: &dyn CredentialSigner<N>, next: Address<N>, activated_at: ReferenceTime) -> Result<(), CustomError> {
+        if activated_at.basis != self.basis {
+            return Err(anyhow!("The key history uses {:?} times, got a {:?} time", self.basis, activated_at.basis).into());
+        }
+        let activated_at = activated_at.value;
+        let previous = self.current();
+        if current.address()? != previous.address {
+            return Err(anyhow!("Only the current key {} can endorse its successor", previous.address).into());
+        }
+        if activated_at <= previous.activated_at {
+            return Err(anyhow!("The next key must activate after {}, got {}", previous.activated_at, activated_at).into());
+        }
+        if self.keys.iter().any(|key| key.address == next) {
+            return Err(anyhow!("Key {} is already in the history", next).into());
+        }
+
+        let endorsement = current.sign(&endorsement_message(self.basis, &previous.address, &next, activated_at)?)?;
+        if let Some(previous) = self.keys.last_mut() {
+            previous.retired_at = Some(activated_at);
+        }
+        self.keys.push(KeyEntry { address: next, activated_at, retired_at: None, endorsement });
+        Ok(())
+    }
+
+    /// Checks that the history starts at the trusted `root` address, whose entry it signed, and that
+    /// every later key is endorsed by its predecessor, with contiguous activation windows.
+    pub fn verify(&self, root: &Address<N>) -> Result<(), CustomError> {
+        if self.network != N::ID {
+            return Err(anyhow!("Key history belongs to network {}, expected {}", self.network, N::ID).into());
+        }
+        let first = self.keys.first().ok_or_else(|| anyhow!("The key history is empty"))?;
+        if first.address != *root {
+            return Err(anyhow!("The key history does not start at the trusted key {}", root).into());
+        }
+        self.basis.validate(first.activated_at)?;
+        let message = endorsement_message(self.basis, root, root, first.activated_at)?;
+        if !verify_signature_with_address_and_message(&first.endorsement, root, &message) {
+            return Err(anyhow!("The root key {} did not sign its activation at {}", root, first.activated_at).into());
+        }
+
+        for (previous, next) in self.keys.iter().zip(self.keys.iter().skip(1)) {
+            if previous.retired_at != Some(next.activated_at) || next.activated_at <= previous.activated_at {
+                return Err(anyhow!("Key {} is not retired when {} activates", previous.address, next.address).into());
+            }
+            let message = endorsement_message(self.basis, &previous.address, &next.address, next.activated_at)?;
+            if !verify_signature_with_address_and_message(&next.endorsement, &previous.address, &message) {
+                return Err(anyhow!("Key {} is not endorsed by {}", next.address, previous.address).into());
+            }
+        }
+        if self.current().retired_at.is_some() {
+            return Err(anyhow!("The current key {} cannot be retired without a successor", self.current().address).into());
+        }
+        Ok(())
+    }
+
+    /// Verifies a credential signature against the key that was active when the credential was
+    /// issued, after verifying the history from `root`. Returns the address of that key.
+    ///
+    /// The issuance time is the signed `issued_at` attribute of the credential, in the time basis of
+    /// the history, and the credential is hashed and checked as in `verify_credential_with_logger`.
+    pub fn verify_signature(&self, root: &Address<N>, message: SignInboundMessage, signature: &Signature<N>, hash: HashAlgorithm, output: HashOutputType, logger: &dyn Logger) -> Result<Address<N>, CustomError> {
+        self.verify(root)?;
+        let issued_at = Validity::from_data(&message.data, self.basis)?
+            .issued_at
+            .ok_or_else(|| CustomError::MissingAttribute(ISSUED_AT_ATTRIBUTE.to_string()))?;
+        let key = self.key_at(issued_at)
+            .ok_or_else(|| anyhow!("No issuer key was active at {}", issued_at))?;
+        let (verified, _) = verify_credential_with_logger(message, signature, &key.address, hash, output, None, logger)?;
+        if !verified {
+            return Err(anyhow!("Credential signature does not match the key {} active at {}", key.address, issued_at).into());
+        }
+        Ok(key.address)
+    }
+}
+
+impl<N: NetworkNative> FromStr for KeyHistory<N> {
+    type Err = CustomError;
+
+    /// Parses a key history from its JSON encoding. It is checked by `verify`.
+    fn from_str(history: &str) -> Result<Self, Self::Err> {
+        let history: Self = serde_json::from_str(history)
+            .map_err(|e| anyhow!("Malformed key history: {}", e))?;
+        if history.keys.is_empty() {
+            return Err(anyhow!("The key history is empty").into());
+        }
+        Ok(history)
+    }
+}
+
+impl<N: NetworkNative> std::fmt::Display for KeyHistory<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::SUBJECT;
+    use serde_json::json;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    fn signer() -> PrivateKeySigner<N> {
+        IssuerAccount::<N>::generate().unwrap().into_signer().unwrap()
+    }
+
+    fn date(value: u32) -> ReferenceTime {
+        ReferenceTime::date(value).unwrap()
+    }
+
+    // Signs a credential issued at the date by the signer.
+    fn sign(signer: &PrivateKeySigner<N>, issued_at: u32) -> (SignInboundMessage, Signature<N>) {
+        let message = SignInboundMessage {
+            data: json!({
+                "issuer": signer.address().unwrap().to_string(),
+                "subject": SUBJECT,
+                "issued_at": format!("{}u32", issued_at),
+            }),
+        };
+        let (signature, _) = sign_message_with_logger(signer, message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).unwrap();
+        (message, Signature::from_str(&signature).unwrap())
+    }
+
+    fn verify(history: &KeyHistory<N>, root: &Address<N>, (message, signature): &(SignInboundMessage, Signature<N>)) -> Result<Address<N>, CustomError> {
+        history.verify_signature(root, message.clone(), signature, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger)
+    }
+
+    #[test]
+    fn test_rotation_and_lookup() {
+        let (first, second, third) = (signer(), signer(), signer());
+        let mut history = KeyHistory::new(&first, date(20240101)).unwrap();
+        history.rotate(&first, second.address().unwrap(), date(20250101)).unwrap();
+        history.rotate(&second, third.address().unwrap(), date(20260101)).unwrap();
+
+        let root = first.address().unwrap();
+        let parsed = KeyHistory::<N>::from_str(&history.to_string()).unwrap();
+        assert_eq!(parsed, history);
+        assert!(parsed.verify(&root).is_ok());
+        assert!(parsed.verify(&second.address().unwrap()).is_err());
+
+        assert!(history.key_at(20231231).is_none());
+        assert_eq!(history.key_at(20241231).unwrap().address, root);
+        assert_eq!(history.key_at(20250101).unwrap().address, second.address().unwrap());
+        assert_eq!(history.current().address, third.address().unwrap());
+
+        // Only the current key can rotate, only forwards in time, and only in the history's basis.
+        assert!(history.rotate(&first, signer().address().unwrap(), date(20270101)).is_err());
+        assert!(history.rotate(&third, signer().address().unwrap(), date(20260101)).is_err());
+        assert!(history.rotate(&third, signer().address().unwrap(), ReferenceTime::block_height(30000000)).is_err());
+    }
+
+    #[test]
+    fn test_signature_checked_against_key_at_issuance() {
+        let (first, second) = (signer(), signer());
+        let mut history = KeyHistory::new(&first, date(20240101)).unwrap();
+        history.rotate(&first, second.address().unwrap(), date(20250101)).unwrap();
+        let root = first.address().unwrap();
+
+        let old = sign(&first, 20240601);
+        assert_eq!(verify(&history, &root, &old).unwrap(), root);
+        let new = sign(&second, 20250601);
+        assert_eq!(verify(&history, &root, &new).unwrap(), second.address().unwrap());
+
+        // The retired key cannot issue after its successor activates, nor backdate past the signed time.
+        assert!(verify(&history, &root, &sign(&first, 20250601)).is_err());
+        let (mut message, signature) = new;
+        message.data["issued_at"] = json!("20240601u32");
+        assert!(verify(&history, &root, &(message.clone(), signature)).is_err());
+
+        message.data.as_object_mut().unwrap().remove("issued_at");
+        assert!(matches!(verify(&history, &root, &(message, signature)), Err(CustomError::MissingAttribute(_))));
+    }
+
+    #[test]
+    fn test_tampered_history_is_rejected() {
+        let (first, second) = (signer(), signer());
+        let mut history = KeyHistory::new(&first, date(20240101)).unwrap();
+        history.rotate(&first, second.address().unwrap(), date(20250101)).unwrap();
+        let root = first.address().unwrap();
+
+        // Swapping in an unendorsed key.
+        let mut forged = history.clone();
+        forged.keys[1].address = signer().address().unwrap();
+        assert!(forged.verify(&root).is_err());
+
+        // Moving the activation time, of a rotated key or of the root key.
+        let mut forged = history.clone();
+        forged.keys[0].retired_at = Some(20240601);
+        forged.keys[1].activated_at = 20240601;
+        assert!(forged.verify(&root).is_err());
+        let mut forged = history.clone();
+        forged.keys[0].activated_at = 20000101;
+        assert!(forged.verify(&root).is_err());
+
+        // Retiring the current key without a successor.
+        let mut forged = history;
+        forged.keys[1].retired_at = Some(20260101);
+        assert!(forged.verify(&root).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+use super::*;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{aead::{Aead, KeyInit, Payload}, XChaCha20Poly1305, XNonce};
+use rand::{rngs::OsRng, RngCore};
+use snarkvm_utilities::{FromBytes, ToBytes};
+use zeroize::Zeroizing;
+
+/// The current keystore file format version.
+pub const KEYSTORE_VERSION: u32 = 1;
+
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER_ALGORITHM: &str = "xchacha20poly1305";
+const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 24;
+const KEY_SIZE: usize = 32;
+
+// Upper bounds on the KDF parameters read from a keystore file, so a crafted file cannot make
+// unlocking allocate more than 256 MiB of memory (four times the default) or run for hours.
+const MAX_MEMORY_COST: u32 = 256 * 1024;
+const MAX_TIME_COST: u32 = 16;
+const MAX_PARALLELISM: u32 = 8;
+
+/// The Argon2id cost parameters used to derive the encryption key from the passphrase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory size in KiB.
+    pub memory_cost: u32,
+    /// Number of iterations.
+    pub time_cost: u32,
+    /// Degree of parallelism.
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self { memory_cost: 64 * 1024, time_cost: 3, parallelism: 1 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KdfSection {
+    algorithm: String,
+    #[serde(flatten)]
+    params: KdfParams,
+    salt: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CipherSection {
+    algorithm: String,
+    nonce: String,
+}
+
+/// The versioned JSON representation of a keystore file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct KeystoreFile {
+    version: u32,
+    network: u16,
+    address: String,
+    kdf: KdfSection,
+    cipher: CipherSection,
+    ciphertext: String,
+}
+
+impl KeystoreFile {
+    // The header fields are authenticated alongside the ciphertext, so they cannot be swapped.
+    fn associated_data(&self) -> Vec<u8> {
+        format!("zpass-keystore:{}:{}:{}", self.version, self.network, self.address).into_bytes()
+    }
+}
+
+/// An issuer private key encrypted at rest under a passphrase.
+///
+/// The key seed is encrypted with XChaCha20-Poly1305 under a key derived from the passphrase
+/// with Argon2id. The issuer address is kept in clear so the keystore can be identified
+/// without unlocking it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keystore<N: NetworkNative> {
+    file: KeystoreFile,
+    address: Address<N>,
+}
+
+impl<N: NetworkNative> Keystore<N> {
+    /// Encrypts the private key under the passphrase with the default KDF parameters.
+    pub fn encrypt(private_key: &PrivateKey<N>, passphrase: &str) -> Result<Self, CustomError> {
+        Self::encrypt_with_params(private_key, passphrase, KdfParams::default())
+    }
+
+    /// Encrypts the private key under the passphrase with the given KDF parameters.
+    pub fn encrypt_with_params(private_key: &PrivateKey<N>, passphrase: &str, params: KdfParams) -> Result<Self, CustomError> {
+        if passphrase.is_empty() {
+            return Err(anyhow!("Keystore passphrase must not be empty").into());
+        }
+        let address = Address::<N>::try_from(private_key)
+            .map_err(|e| anyhow!("Failed to parse issuer address: {}", e))?;
+
+        let mut salt = [0u8; SALT_SIZE];
+        let mut nonce = [0u8; NONCE_SIZE];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let mut file = KeystoreFile {
+            version: KEYSTORE_VERSION,
+            network: N::ID,
+            address: address.to_string(),
+            kdf: KdfSection { algorithm: KDF_ALGORITHM.to_string(), params, salt: hex::encode(salt) },
+            cipher: CipherSection { algorithm: CIPHER_ALGORITHM.to_string(), nonce: hex::encode(nonce) },
+            ciphertext: String::new(),
+        };
+
+        let key = derive_key(passphrase, &salt, &params)?;
+        let seed = Zeroizing::new(private_key.to_bytes_le()
+            .map_err(|e| anyhow!("Failed to serialize private key: {}", e))?);
+        let ciphertext = XChaCha20Poly1305::new(key.as_slice().into())
+            .encrypt(XNonce::from_slice(&nonce), Payload { msg: seed.as_slice(), aad: &file.associated_data() })
+            .map_err(|_| anyhow!("Failed to encrypt private key"))?;
+        file.ciphertext = hex::encode(ciphertext);
+
+        Ok(Self { file, address })
+    }
+
+    /// Parses a keystore from its JSON file contents.
+    pub fn from_json(json: &str) -> Result<Self, CustomError> {
+        let file: KeystoreFile = serde_json::from_str(json)
+            .map_err(|e| anyhow!("Malformed keystore file: {}", e))?;
+
+        if file.version != KEYSTORE_VERSION {
+            return Err(anyhow!("Unsupported keystore version {}", file.version).into());
+        }
+        if file.network != N::ID {
+            return Err(anyhow!("Keystore belongs to network {}, expected {}", file.network, N::ID).into());
+        }
+        if file.kdf.algorithm != KDF_ALGORITHM || file.cipher.algorithm != CIPHER_ALGORITHM {
+            return Err(anyhow!("Unsupported keystore algorithms {} / {}", file.kdf.algorithm, file.cipher.algorithm).into());
+        }
+        let address = Address::<N>::from_str(&file.address)
+            .map_err(|e| anyhow!("Invalid keystore address: {}", e))?;
+
+        Ok(Self { file, address })
+    }
+
+    /// Serializes the keystore into its JSON file contents.
+    pub fn to_json(&self) -> Result<String, CustomError> {
+        Ok(serde_json::to_string_pretty(&self.file)
+            .map_err(|e| anyhow!("Failed to serialize keystore: {}", e))?)
+    }
+
+    /// Returns the issuer address of the encrypted key.
+    pub fn address(&self) -> Address<N> {
+        self.address
+    }
+
+    /// Decrypts the private key with the passphrase.
+    pub fn decrypt(&self, passphrase: &str) -> Result<SecretPrivateKey<N>, CustomError> {
+        let salt = decode_hex(&self.file.kdf.salt, SALT_SIZE, "salt")?;
+        let nonce = decode_hex(&self.file.cipher.nonce, NONCE_SIZE, "nonce")?;
+        let ciphertext = hex::decode(&self.file.ciphertext)
+            .map_err(|e| anyhow!("Invalid keystore ciphertext: {}", e))?;
+
+        let key = derive_key(passphrase, &salt, &self.file.kdf.params)?;
+        let seed = Zeroizing::new(XChaCha20Poly1305::new(key.as_slice().into())
+            .decrypt(XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &self.file.associated_data() })
+            .map_err(|_| anyhow!("Failed to decrypt keystore: wrong passphrase or corrupted file"))?);
+
+        let private_key = SecretPrivateKey::new(PrivateKey::<N>::from_bytes_le(&seed)
+            .map_err(|e| anyhow!("Failed to parse decrypted private key: {}", e))?);
+        if Address::<N>::try_from(private_key.expose())? != self.address {
+            return Err(anyhow!("Decrypted private key does not match the keystore address").into());
+        }
+        Ok(private_key)
+    }
+
+    /// Decrypts the private key with the passphrase into a signer.
+    pub fn unlock(&self, passphrase: &str) -> Result<PrivateKeySigner<N>, CustomError> {
+        PrivateKeySigner::new(self.decrypt(passphrase)?)
+    }
+
+    /// Re-encrypts the key under a new passphrase, with a fresh salt and nonce.
+    pub fn change_passphrase(&self, old_passphrase: &str, new_passphrase: &str) -> Result<Self, CustomError> {
+        let private_key = self.decrypt(old_passphrase)?;
+        Self::encrypt_with_params(private_key.expose(), new_passphrase, self.file.kdf.params)
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], params: &KdfParams) -> Result<Zeroizing<[u8; KEY_SIZE]>, CustomError> {
+    if params.memory_cost > MAX_MEMORY_COST || params.time_cost > MAX_TIME_COST || params.parallelism > MAX_PARALLELISM {
+        return Err(anyhow!(
+            "Keystore KDF parameters exceed the maximums of memory_cost {}, time_cost {} and parallelism {}",
+            MAX_MEMORY_COST, MAX_TIME_COST, MAX_PARALLELISM
+        ).into());
+    }
+    let params = Params::new(params.memory_cost, params.time_cost, params.parallelism, Some(KEY_SIZE))
+        .map_err(|e| anyhow!("Invalid keystore KDF parameters: {}", e))?;
+    let mut key = Zeroizing::new([0u8; KEY_SIZE]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut_slice())
+        .map_err(|e| anyhow!("Failed to derive keystore key: {}", e))?;
+    Ok(key)
+}
+
+fn decode_hex(value: &str, size: usize, name: &str) -> Result<Vec<u8>, CustomError> {
+    let bytes = hex::decode(value).map_err(|e| anyhow!("Invalid keystore {}: {}", name, e))?;
+    if bytes.len() != size {
+        return Err(anyhow!("Keystore {} must be {} bytes", name, size).into());
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, SUBJECT};
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    // Cheap parameters to keep the tests fast.
+    const TEST_PARAMS: KdfParams = KdfParams { memory_cost: 256, time_cost: 1, parallelism: 1 };
+
+    fn keystore() -> Keystore<N> {
+        let private_key = PrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        Keystore::encrypt_with_params(&private_key, "correct horse", TEST_PARAMS).unwrap()
+    }
+
+    #[test]
+    fn test_keystore_roundtrip() {
+        let keystore = keystore();
+        let json = keystore.to_json().unwrap();
+        assert!(!json.contains(PRIVATE_KEY));
+
+        let parsed = Keystore::<N>::from_json(&json).unwrap();
+        assert_eq!(parsed, keystore);
+        assert_eq!(parsed.decrypt("correct horse").unwrap().expose().to_string(), PRIVATE_KEY);
+
+        let signer = parsed.unlock("correct horse").unwrap();
+        assert_eq!(signer.address().unwrap(), keystore.address());
+    }
+
+    #[test]
+    fn test_keystore_wrong_passphrase() {
+        let keystore = keystore();
+        assert!(keystore.decrypt("wrong horse").is_err());
+        assert!(keystore.unlock("").is_err());
+    }
+
+    #[test]
+    fn test_keystore_change_passphrase() {
+        let keystore = keystore();
+        let changed = keystore.change_passphrase("correct horse", "battery staple").unwrap();
+
+        assert!(changed.decrypt("correct horse").is_err());
+        assert_eq!(changed.decrypt("battery staple").unwrap().expose().to_string(), PRIVATE_KEY);
+        assert!(keystore.change_passphrase("wrong horse", "battery staple").is_err());
+    }
+
+    #[test]
+    fn test_keystore_rejects_tampering() {
+        let json = keystore().to_json().unwrap();
+
+        let other = SUBJECT;
+        let mut file: JsonValue = serde_json::from_str(&json).unwrap();
+        file["address"] = JsonValue::from(other);
+        let tampered = Keystore::<N>::from_json(&file.to_string()).unwrap();
+        assert!(tampered.decrypt("correct horse").is_err());
+
+        let mut file: JsonValue = serde_json::from_str(&json).unwrap();
+        file["version"] = JsonValue::from(2);
+        assert!(Keystore::<N>::from_json(&file.to_string()).is_err());
+
+        assert!(Keystore::<MainnetV0>::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_keystore_rejects_excessive_kdf_params() {
+        let json = keystore().to_json().unwrap();
+        for (param, value) in [("memory_cost", MAX_MEMORY_COST + 1), ("time_cost", MAX_TIME_COST + 1), ("parallelism", MAX_PARALLELISM + 1)] {
+            let mut file: JsonValue = serde_json::from_str(&json).unwrap();
+            file["kdf"][param] = JsonValue::from(value);
+            let keystore = Keystore::<N>::from_json(&file.to_string()).unwrap();
+            assert!(keystore.decrypt("correct horse").unwrap_err().to_string().contains("exceed"));
+        }
+
+        let private_key = PrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let params = KdfParams { time_cost: u32::MAX, ..TEST_PARAMS };
+        assert!(Keystore::encrypt_with_params(&private_key, "correct horse", params).is_err());
+    }
+}
@@ -6,9 +6,57 @@ mod helpers;
 pub mod wasm;
 mod error;
 mod merkle_tree;
+mod hasher;
+mod signer;
+mod keystore;
+mod secret;
+mod shamir;
+mod mnemonic;
+mod account;
+mod checks;
+mod multisig;
+mod delegation;
+mod key_history;
+mod report;
+mod validity;
+mod date;
+mod country;
+mod text;
+mod decimal;
+mod program;
+mod transition;
+mod commitment;
+mod record;
+#[cfg(test)]
+mod test_fixtures;
 
 // Crate level imports
 pub use wasm::*;
+pub use helpers::{ConsoleLogger, Logger, StdoutLogger};
+pub use merkle_tree::{MerkleTree, hash_inputs_size_8, sign_root};
+pub use signer::{CredentialSigner, PrivateKeySigner, RemoteSigner, RpcTransport, JsFunctionTransport, handle_rpc_request, RPC_METHOD_ADDRESS, RPC_METHOD_SIGN};
+#[cfg(not(target_arch = "wasm32"))]
+pub use signer::TcpTransport;
+pub use secret::{SecretPrivateKey, SecretString};
+pub use shamir::{KeyShare, KEY_SHARE_VERSION, recover_private_key, split_private_key};
+pub use account::{IssuerAccount, parse_address_normalized};
+pub use mnemonic::{MNEMONIC_WORD_COUNTS, derive_issuer_account, generate_mnemonic, validate_mnemonic};
+pub use keystore::{Keystore, KdfParams, KEYSTORE_VERSION};
+pub use checks::{SignChecks, ISSUER_ATTRIBUTE, SUBJECT_ATTRIBUTE};
+pub use multisig::{IssuerPolicy, IssuerSignature, MultiIssuerCredential};
+pub use delegation::{SCHEMA_ATTRIBUTE, DelegatedSignature, DelegationCertificate, DelegationClaims, DelegationOptions, credential_schema, sign_message_with_delegation, verify_delegation_chain};
+pub use key_history::{KeyEntry, KeyHistory};
+pub use program::{ProgramStructs, sign_program_struct};
+pub use record::{ZPassAttributes, ZPassRecord, ZPassVariant};
+pub use commitment::{HIDDEN_ATTRIBUTES, generate_salt, hiding_commitments, hiding_invalidation_commitment, invalidation_commitment, issuance_commitment, parse_salt, verify_opening};
+pub use transition::{supported_transitions, transition_inputs};
+pub use decimal::{DecimalAttribute, DecimalType, MAX_DECIMAL_SCALE};
+pub use text::{TEXT_BYTES_PER_FIELD, TextAttribute, decode_text, normalize_text};
+pub use country::{COUNTRIES, Country, encode_country};
+pub use date::{Date, DateEncoding, is_encoded_date_before};
+pub use validity::{EXPIRY_ATTRIBUTE, ISSUED_AT_ATTRIBUTE, NOT_BEFORE_ATTRIBUTE, ReferenceTime, TimeBasis, Validity};
+pub use report::{CheckResult, CheckStatus, Revocation, VerificationOptions, VerificationReport, verify_credential_report};
+pub use hasher::{AlgorithmHasher, CredentialHasher, HasherRegistry, get_hasher, register_hasher, with_global_registry};
 
 // Standard library imports
 use std::convert::TryFrom;
@@ -16,9 +64,9 @@ use std::str::FromStr;
 
 // External crate imports
 use snarkvm_console::{
-    prelude::Parser, account::{PrivateKey, Signature}, network::{environment::ToFields, TestnetV0, MainnetV0, Network as NetworkNative}, prelude::Zero, program::{Identifier, Literal, Plaintext, Value}, types::{*, field::Add}
+    prelude::Parser, account::{PrivateKey, Signature}, network::{environment::ToFields, TestnetV0, MainnetV0, Network as NetworkNative}, prelude::Zero, program::{Identifier, Literal, LiteralType, Plaintext, Value}, types::{*, field::Add}
 };
-use snarkvm_utilities::{TestRng, ToBits, Uniform};
+use snarkvm_utilities::{ToBits, Uniform};
 
 use indexmap::IndexMap;
 use wasm_bindgen::prelude::*;
@@ -29,7 +77,7 @@ use anyhow::anyhow;
 // Internal module imports
 use crate::error::CustomError;
 use crate::helpers::{
-    ConsoleLogger, Logger, create_hash, generate_message_with_addresses_and_fields,
+    create_hash, generate_message_with_addresses_and_fields, hash_bytes_to_field, insert_to_map,
     sign_message_with_private_key, string_to_field, string_to_value_fields,
     verify_signature_with_address_and_message, convert_data_to_struct
 };
@@ -39,45 +87,55 @@ struct Credential<N: NetworkNative> {
     data: IndexMap<String, Plaintext<N>>,
 }
 
-/// Signs the provided message using the given private key.
+/// Signs the provided message using the given issuer signer.
 ///
 /// This function also logs various stages of the signing process using the provided logger.
 ///
 /// # Parameters
 ///
-/// - `private_key`: A string representing the private key to sign the message.
+/// - `signer`: The issuer signer, e.g. a `PrivateKeySigner` or a `RemoteSigner`.
 /// - `message`: The message to be signed, wrapped inside `SignInboundMessage` struct.
 /// - `hash`: The hash algorithm to be used.
+/// - `output`: The type the hash is mapped to, as with Leo's `hash_to_*` functions.
 /// - `logger`: Logger to log various stages of the signing process.
 ///
 /// # Returns
 ///
 /// A result with tuple of signature and hash as strings if successful, otherwise returns a `CustomError`.
-pub fn sign_message_with_logger<N: NetworkNative>(private_key: String, message: SignInboundMessage, hash: HashAlgorithm, logger: &dyn Logger) -> Result<(String, String), CustomError> {
-    let private_key = PrivateKey::<N>::from_str(&private_key)
-        .map_err(|e| anyhow!("Failed to parse private key: {}", e))?;
-    let issuer = Address::<N>::try_from(&private_key)
-        .map_err(|e| anyhow!("Failed to parse issuer address: {}", e))?;
+pub fn sign_message_with_logger<N: NetworkNative>(signer: &dyn CredentialSigner<N>, message: SignInboundMessage, hash: HashAlgorithm, output: HashOutputType, logger: &dyn Logger) -> Result<(String, String), CustomError> {
+    sign_message_with_checks(signer, message, hash, output, &SignChecks::default(), logger)
+}
 
-    let data = convert_data_to_struct(message.data, logger);
+/// Signs the provided message like `sign_message_with_logger`, after running `checks` on it.
+///
+/// # Returns
+///
+/// A result with tuple of signature and hash as strings if successful, otherwise returns a `CustomError`,
+/// such as `CustomError::IssuerMismatch` when a check fails.
+pub fn sign_message_with_checks<N: NetworkNative>(signer: &dyn CredentialSigner<N>, message: SignInboundMessage, hash: HashAlgorithm, output: HashOutputType, checks: &SignChecks, logger: &dyn Logger) -> Result<(String, String), CustomError> {
+    checks.check(&message.data, &signer.address()?)?;
 
-    logger.log(&format!("Income Hash Algo: {:?}", hash));
+    let hash = hash_credential::<N>(message, hash, output, logger)?;
+    let signature = sign_hash(signer, &hash, logger)?;
 
-    let credential  = Credential {
-        data
-    };
-
-    let credentials_message: Value<N> = generate_message_with_addresses_and_fields(credential)?;
-    let hash = create_hash(credentials_message.clone(), hash)?;
-    let mut rng = TestRng::default();
+    Ok((signature, hash))
+}
 
-    let hash_fields = string_to_value_fields(hash.to_string().as_str());
+/// Signs a credential hash such as `123field` with the issuer signer, checking the signature
+/// against the signer address before returning it.
+///
+/// Every signing pipeline ends here, whichever way the hash was computed.
+///
+/// # Returns
+///
+/// A result with the signature as a string if successful, otherwise returns a `CustomError`.
+pub fn sign_hash<N: NetworkNative>(signer: &dyn CredentialSigner<N>, hash: &str, logger: &dyn Logger) -> Result<String, CustomError> {
+    let issuer = signer.address()?;
+    let hash_fields = Value::<N>::from_str(hash)
+        .map_err(|e| anyhow!("Failed to parse hash '{}': {}", hash, e))?
+        .to_fields()?;
 
-    let (signature, _nonce) = sign_message_with_private_key(
-        &private_key,
-        hash_fields.as_slice(),
-        &mut rng
-    )?;
+    let signature = signer.sign(hash_fields.as_slice())?;
 
     let verified = verify_signature_with_address_and_message(
         &signature,
@@ -85,15 +143,98 @@ pub fn sign_message_with_logger<N: NetworkNative>(private_key: String, message:
         hash_fields.as_slice()
     );
 
-    assert!(verified, "Signature was not verified properly!");
+    if !verified {
+        return Err(anyhow!("Signature does not verify against the issuer address {}", issuer).into());
+    }
 
-    logger.log(&format!("Message: {:?}", credentials_message));
     logger.log(&format!("Signature: {:?}", signature));
     logger.log(&format!("Verified: {:?}", verified));
     logger.log(&format!("Hash: {:?}", hash));
 
+    Ok(signature.to_string())
+}
+
+/// Computes the hash of the credential in the message, as signed by `sign_message_with_logger`.
+///
+/// # Returns
+///
+/// A result with the hash as a string if successful, otherwise returns a `CustomError`.
+pub fn hash_credential<N: NetworkNative>(message: SignInboundMessage, hash: HashAlgorithm, output: HashOutputType, logger: &dyn Logger) -> Result<String, CustomError> {
+    hash_credential_struct::<N>(convert_data_to_struct(message.data, logger)?, hash, output, logger)
+}
+
+// Hashes the credential attributes once converted by `convert_data_to_struct`.
+pub(crate) fn hash_credential_struct<N: NetworkNative>(data: IndexMap<String, Plaintext<N>>, hash: HashAlgorithm, output: HashOutputType, logger: &dyn Logger) -> Result<String, CustomError> {
+    logger.log(&format!("Income Hash Algo: {:?}", hash));
+
+    let credential  = Credential {
+        data
+    };
+
+    let credentials_message: Value<N> = generate_message_with_addresses_and_fields(credential)?;
+    let hash = create_hash(credentials_message.clone(), hash, output)?;
+
+    logger.log(&format!("Message: {:?}", credentials_message));
+
+    Ok(hash)
+}
+
+/// Verifies an issuer signature over the credential in the message.
+///
+/// The credential is hashed exactly as `sign_message_with_logger` does, so verifiers only need
+/// the attributes and not a pre-computed hash. The issuer attribute, if present, must be `issuer`,
+/// and when a reference time is given the credential must be inside its validity window then.
+///
+/// # Returns
+///
+/// A result with tuple of the verification outcome and the recomputed hash if the credential could be
+/// hashed, otherwise returns a `CustomError`.
+pub fn verify_credential_with_logger<N: NetworkNative>(message: SignInboundMessage, signature: &Signature<N>, issuer: &Address<N>, hash: HashAlgorithm, output: HashOutputType, at: Option<ReferenceTime>, logger: &dyn Logger) -> Result<(bool, String), CustomError> {
+    SignChecks::verification().check(&message.data, issuer)?;
+    Validity::check_data(&message.data, at)?;
+
+    let hash = hash_credential::<N>(message, hash, output, logger)?;
+    let verified = verify_hash(signature, issuer, &hash)?;
+
+    logger.log(&format!("Verified: {:?}", verified));
+
+    Ok((verified, hash))
+}
+
+// Checks the signature over the credential hash, as `sign_hash` signs it.
+pub(crate) fn verify_hash<N: NetworkNative>(signature: &Signature<N>, issuer: &Address<N>, hash: &str) -> Result<bool, CustomError> {
+    let hash_fields = Value::<N>::from_str(hash)
+        .map_err(|e| anyhow!("Failed to parse hash '{}': {}", hash, e))?
+        .to_fields()?;
+    Ok(verify_signature_with_address_and_message(signature, issuer, hash_fields.as_slice()))
+}
+
+/// Signs the provided message using the given issuer signer and a pluggable credential hasher.
+///
+/// Behaves like `sign_message_with_logger`, except the credential struct is hashed by `hasher`
+/// (e.g. one looked up with `get_hasher`) instead of a built-in `HashAlgorithm`. The `checks`
+/// are run on the message first.
+///
+/// # Returns
+///
+/// A result with tuple of signature and hash as strings if successful, otherwise returns a `CustomError`.
+pub fn sign_message_with_hasher<N: NetworkNative>(signer: &dyn CredentialSigner<N>, message: SignInboundMessage, hasher: &dyn CredentialHasher<N>, checks: &SignChecks, logger: &dyn Logger) -> Result<(String, String), CustomError> {
+    checks.check(&message.data, &signer.address()?)?;
+
+    let data = convert_data_to_struct(message.data, logger)?;
+
+    logger.log(&format!("Income Hasher: {}", hasher.name()));
+
+    let credentials_message: Value<N> = generate_message_with_addresses_and_fields(Credential { data })?;
+    let hash = hasher.hash(&credentials_message)
+        .map_err(|e| anyhow!("Hasher '{}' failed: {}", hasher.name(), e))?
+        .to_string();
 
-    Ok((signature.to_string(), hash.to_string()))
+    logger.log(&format!("Message: {:?}", credentials_message));
+
+    let signature = sign_hash(signer, &hash, logger)?;
+
+    Ok((signature, hash))
 }
 
 
@@ -108,9 +249,9 @@ mod tests {
     type N = TestnetV0;
 
     fn get_test_data() -> (String, String, String, String) {
-        let private_key = "APrivateKey1zkp5LqRmm7535XfiX77VPQEgsS2Dj1B2DvH4QNP1UYrHEoR".to_string();
-        let issuer = "aleo1ekyuzclmcw3aj7qncsxxaapxem82mgrd8zadgrrvl5k705zx6q9s7usuqy".to_string();
-        let subject = "aleo14w44zfrehup9g894j7tgeyz5gsjuxn0nfn09vd2fvpznrg85rs8skywkte".to_string();
+        let private_key = test_fixtures::PRIVATE_KEY.to_string();
+        let issuer = test_fixtures::ISSUER.to_string();
+        let subject = test_fixtures::SUBJECT.to_string();
         let dob = "20000101scalar".to_string();
         (private_key, issuer, subject, dob)
     }
@@ -129,20 +270,101 @@ mod tests {
         let message = SignInboundMessage {
             data: json_value,
         };
-        let result = sign_message_with_logger::<N>(private_key, message, HashAlgorithm::POSEIDON2, &StdoutLogger);
+        let signer = PrivateKeySigner::<N>::from_str(&private_key).unwrap();
+        let result = sign_message_with_logger::<N>(&signer, message, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger);
 
         assert!(result.is_ok());
 
         let (_, _) = result.unwrap();
     }
 
+    #[test]
+    fn test_sign_message_with_hasher_matches_algorithm() {
+        let (private_key, issuer, subject, dob) = get_test_data();
+        let message = SignInboundMessage {
+            data: json!({ "issuer": issuer, "subject": subject, "dob": dob }),
+        };
+
+        let signer = PrivateKeySigner::<N>::from_str(&private_key).unwrap();
+        let hasher = get_hasher::<N>("poseidon2").unwrap();
+        let (signature, hash) = sign_message_with_hasher::<N>(&signer, message.clone(), hasher.as_ref(), &SignChecks::default(), &StdoutLogger).unwrap();
+        let (_, expected_hash) = sign_message_with_logger::<N>(&signer, message, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).unwrap();
+
+        assert_eq!(hash, expected_hash);
+        let signature = Signature::<N>::from_str(&signature).unwrap();
+        let issuer = Address::<N>::from_str(&issuer).unwrap();
+        assert!(verify_signature_with_address_and_message(&signature, &issuer, &string_to_value_fields::<N>(&hash)));
+    }
+
+    #[test]
+    fn test_sign_message_rejects_mismatched_issuer() {
+        let (private_key, _, subject, dob) = get_test_data();
+        let signer = PrivateKeySigner::<N>::from_str(&private_key).unwrap();
+        let message = SignInboundMessage {
+            data: json!({ "issuer": subject, "subject": subject, "dob": dob }),
+        };
+
+        let result = sign_message_with_logger::<N>(&signer, message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger);
+        assert!(matches!(result, Err(CustomError::IssuerMismatch { .. })));
+
+        let result = sign_message_with_checks::<N>(&signer, message, HashAlgorithm::POSEIDON2, HashOutputType::Field, &SignChecks::none(), &StdoutLogger);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_verify_credential_from_attributes() {
+        let (private_key, issuer, subject, dob) = get_test_data();
+        let message = SignInboundMessage {
+            data: json!({ "issuer": issuer, "subject": subject, "dob": dob }),
+        };
+        let signer = PrivateKeySigner::<N>::from_str(&private_key).unwrap();
+        let (signature, hash) = sign_message_with_logger::<N>(&signer, message.clone(), HashAlgorithm::BHP1024, HashOutputType::Field, &StdoutLogger).unwrap();
+        let signature = Signature::<N>::from_str(&signature).unwrap();
+        let issuer = Address::<N>::from_str(&issuer).unwrap();
+
+        let (verified, recomputed) = verify_credential_with_logger::<N>(message, &signature, &issuer, HashAlgorithm::BHP1024, HashOutputType::Field, None, &StdoutLogger).unwrap();
+        assert!(verified);
+        assert_eq!(recomputed, hash);
+
+        let altered = SignInboundMessage {
+            data: json!({ "issuer": issuer.to_string(), "subject": subject, "dob": "20010101scalar" }),
+        };
+        let (verified, _) = verify_credential_with_logger::<N>(altered.clone(), &signature, &issuer, HashAlgorithm::BHP1024, HashOutputType::Field, None, &StdoutLogger).unwrap();
+        assert!(!verified);
+
+        let (verified, _) = verify_credential_with_logger::<N>(altered, &signature, &issuer, HashAlgorithm::POSEIDON2, HashOutputType::Field, None, &StdoutLogger).unwrap();
+        assert!(!verified);
+
+        let malformed = SignInboundMessage { data: json!({ "dob": "notascalar" }) };
+        assert!(verify_credential_with_logger::<N>(malformed, &signature, &issuer, HashAlgorithm::BHP1024, HashOutputType::Field, None, &StdoutLogger).is_err());
+    }
+
+    #[test]
+    fn test_verify_expired_credential() {
+        let (private_key, issuer, subject, dob) = get_test_data();
+        let message = SignInboundMessage {
+            data: json!({ "issuer": issuer, "subject": subject, "dob": dob, "expiry": "20200101u32" }),
+        };
+        let signer = PrivateKeySigner::<N>::from_str(&private_key).unwrap();
+        let checks = SignChecks::default().at(ReferenceTime::date(20190101).unwrap());
+        let (signature, _) = sign_message_with_checks::<N>(&signer, message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger).unwrap();
+        let signature = Signature::<N>::from_str(&signature).unwrap();
+        let issuer = Address::<N>::from_str(&issuer).unwrap();
+        let verify = |at: Option<ReferenceTime>| verify_credential_with_logger::<N>(message.clone(), &signature, &issuer, HashAlgorithm::POSEIDON2, HashOutputType::Field, at, &StdoutLogger);
+
+        // Only the reference time the verifier gives is checked, never today's date.
+        assert!(verify(None).unwrap().0);
+        assert!(verify(Some(ReferenceTime::date(20191231).unwrap())).unwrap().0);
+        assert!(matches!(verify(Some(ReferenceTime::date(20200102).unwrap())), Err(CustomError::Expired { expiry: 20200101, time: 20200102 })));
+    }
+
     #[test]
     fn test_create_hash_with_different_messages_psd2() {
         let message1 = string_to_value::<N>("123field");
         let message2 = string_to_value::<N>("321field");
 
-        let hash1 = create_hash(message1, HashAlgorithm::POSEIDON2).unwrap();
-        let hash2 = create_hash(message2, HashAlgorithm::POSEIDON2).unwrap();
+        let hash1 = create_hash(message1, HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap();
+        let hash2 = create_hash(message2, HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap();
 
         assert_ne!(hash1, hash2, "Hashes of different messages should be different");
     }
@@ -152,8 +374,8 @@ mod tests {
         let message1 = string_to_value::<N>("123field");
         let message2 = string_to_value::<N>("321field");
 
-        let hash1 = create_hash(message1, HashAlgorithm::BHP1024).unwrap();
-        let hash2 = create_hash(message2, HashAlgorithm::BHP1024).unwrap();
+        let hash1 = create_hash(message1, HashAlgorithm::BHP1024, HashOutputType::Field).unwrap();
+        let hash2 = create_hash(message2, HashAlgorithm::BHP1024, HashOutputType::Field).unwrap();
 
         assert_ne!(hash1, hash2, "Hashes of different messages should be different");
     }
@@ -163,8 +385,8 @@ mod tests {
         let message = string_to_value::<N>("123field");
 
 
-        let hash1 = create_hash(message.clone(), HashAlgorithm::POSEIDON2).unwrap();
-        let hash2 = create_hash(message.clone(), HashAlgorithm::POSEIDON2).unwrap();
+        let hash1 = create_hash(message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap();
+        let hash2 = create_hash(message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap();
 
         println!("{}", hash1.clone());
         println!("{}", hash2.clone());
@@ -176,8 +398,8 @@ mod tests {
     fn test_create_hash_with_same_messages_bhp2014() {
         let message = string_to_value::<N>("123field");
 
-        let hash1 = create_hash(message.clone(), HashAlgorithm::BHP1024).unwrap();
-        let hash2 = create_hash(message.clone(), HashAlgorithm::BHP1024).unwrap();
+        let hash1 = create_hash(message.clone(), HashAlgorithm::BHP1024, HashOutputType::Field).unwrap();
+        let hash2 = create_hash(message.clone(), HashAlgorithm::BHP1024, HashOutputType::Field).unwrap();
 
         println!("{}", hash1.clone());
         println!("{}", hash2.clone());
@@ -189,8 +411,8 @@ mod tests {
     fn test_create_hash_with_same_messages_sha3() {
         let message = string_to_value::<N>("123field");
 
-        let hash1 = create_hash(message.clone(), HashAlgorithm::SHA3_256).unwrap();
-        let hash2 = create_hash(message.clone(), HashAlgorithm::SHA3_256).unwrap();
+        let hash1 = create_hash(message.clone(), HashAlgorithm::SHA3_256, HashOutputType::Field).unwrap();
+        let hash2 = create_hash(message.clone(), HashAlgorithm::SHA3_256, HashOutputType::Field).unwrap();
 
         println!("{}", hash1.clone());
         println!("{}", hash2.clone());
@@ -202,8 +424,8 @@ mod tests {
     fn test_create_hash_with_same_messages_keccak256() {
         let message = string_to_value::<N>("123field");
 
-        let hash1 = create_hash(message.clone(), HashAlgorithm::KECCAK256).unwrap();
-        let hash2 = create_hash(message.clone(), HashAlgorithm::KECCAK256).unwrap();
+        let hash1 = create_hash(message.clone(), HashAlgorithm::KECCAK256, HashOutputType::Field).unwrap();
+        let hash2 = create_hash(message.clone(), HashAlgorithm::KECCAK256, HashOutputType::Field).unwrap();
 
         println!("{}", hash1.clone());
         println!("{}", hash2.clone());
@@ -211,6 +433,41 @@ mod tests {
         assert_eq!(hash1, hash2, "Hashes of the same message should be the same");
     }
 
+    #[test]
+    fn test_create_hash_field_output_matches_native_hash() {
+        let message = string_to_value::<N>("123field");
+
+        let psd2 = create_hash(message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap();
+        let bhp1024 = create_hash(message.clone(), HashAlgorithm::BHP1024, HashOutputType::Field).unwrap();
+
+        let expected_psd2 = N::hash_psd2(&message.to_fields().unwrap()).unwrap();
+        let expected_bhp1024 = N::hash_bhp1024(&message.to_bits_le()).unwrap();
+
+        assert_eq!(psd2, expected_psd2.to_string());
+        assert_eq!(bhp1024, expected_bhp1024.to_string());
+    }
+
+    #[test]
+    fn test_create_hash_output_types() {
+        let message = string_to_value::<N>("123field");
+
+        let group = create_hash(message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Group).unwrap();
+        let expected_group = N::hash_to_group_psd2(&message.to_fields().unwrap()).unwrap();
+        assert_eq!(group, expected_group.to_string());
+
+        let address = create_hash(message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Address).unwrap();
+        assert_eq!(address, Address::<N>::new(expected_group).to_string());
+
+        let scalar = create_hash(message.clone(), HashAlgorithm::KECCAK256, HashOutputType::Scalar).unwrap();
+        assert!(scalar.ends_with("scalar"));
+
+        let small = create_hash(message.clone(), HashAlgorithm::SHA3_256, HashOutputType::U8).unwrap();
+        assert!(U8::<N>::from_str(&small).is_ok());
+
+        let signed = create_hash(message, HashAlgorithm::BHP1024, HashOutputType::I128).unwrap();
+        assert!(I128::<N>::from_str(&signed).is_ok());
+    }
+
     #[test]
     fn test_generate_message() {
         let (_private_key, issuer, subject, dob) = get_test_data();
@@ -223,7 +480,7 @@ mod tests {
         });
 
         let credential = Credential::<N> {
-            data: convert_data_to_struct(json_value, &StdoutLogger),
+            data: convert_data_to_struct(json_value, &StdoutLogger).unwrap(),
         };
 
         println!("{:?}", credential);
@@ -232,4 +489,14 @@ mod tests {
 
         assert!(result.is_ok(), "Message generation should be successful");
     }
+
+    #[test]
+    fn test_hash_ignores_attribute_order() {
+        let (_private_key, issuer, subject, dob) = get_test_data();
+        let hash = |data: JsonValue| hash_credential::<N>(SignInboundMessage { data }, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).unwrap();
+
+        let forward = serde_json::from_str(&format!(r#"{{"dob":"{}","issuer":"{}","subject":"{}"}}"#, dob, issuer, subject)).unwrap();
+        let backward = serde_json::from_str(&format!(r#"{{"subject":"{}","issuer":"{}","dob":"{}"}}"#, subject, issuer, dob)).unwrap();
+        assert_eq!(hash(forward), hash(backward));
+    }
 }
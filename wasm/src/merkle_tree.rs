@@ -47,7 +47,7 @@ impl<N: NetworkNative> MerkleTree<N> {
 
         // Skip the last level (root) by using .len() - 1
         for level in &self.levels[..self.levels.len() - 1] {
-            if current_index % 2 == 0 {
+            if current_index.is_multiple_of(2) {
                 proof.push(level[current_index + 1]);
             } else {
                 proof.push(level[current_index - 1]);
@@ -58,14 +58,19 @@ impl<N: NetworkNative> MerkleTree<N> {
         Ok(proof)
     }
 
-    pub fn verify_proof(&self, leaf: Field<N>, proof: &Vec<Field<N>>) -> Result<bool, CustomError> {
+    pub fn verify_proof(&self, leaf: Field<N>, proof: &[Field<N>]) -> Result<bool, CustomError> {
+        Ok(Self::root_of_proof(leaf, proof)? == self.root)
+    }
+
+    /// Computes the root the proof of the leaf leads to, for checking proofs against a known root.
+    pub fn root_of_proof(leaf: Field<N>, proof: &[Field<N>]) -> Result<Field<N>, CustomError> {
         let mut current_hash = leaf;
         for proof_element in proof {
             current_hash = Self::hash_field_sum(&current_hash, proof_element)?;
             println!("Current hash: {}", current_hash);
         }
 
-        Ok(current_hash == self.root)
+        Ok(current_hash)
     }
 
     pub fn root(&self) -> Field<N> {
@@ -77,33 +82,11 @@ impl<N: NetworkNative> MerkleTree<N> {
     }
 }
 
-pub fn sign_root<N: NetworkNative>(private_key: &str, root: &str) -> Result<String, CustomError> {
-    if !private_key.starts_with("APrivateKey1") {
-        return Err(CustomError::from(anyhow::anyhow!("Private key must start with APrivateKey1")));
-    }
-
+pub fn sign_root<N: NetworkNative>(signer: &dyn CredentialSigner<N>, root: &str, logger: &dyn Logger) -> Result<String, CustomError> {
     if !root.ends_with("field") {
         return Err(CustomError::from(anyhow::anyhow!("Root must end with 'field'")));
     }
-    let private_key = PrivateKey::<N>::from_str(private_key)?;
-    let issuer = Address::<N>::try_from(&private_key)?;
-    let hash_fields = string_to_value_fields::<N>(root);
-    let mut rng = TestRng::default();
-
-    let (signature, _nonce) = sign_message_with_private_key(
-        &private_key,
-        hash_fields.as_slice(),
-        &mut rng
-    )?;
-
-    let verified = verify_signature_with_address_and_message(
-        &signature,
-        &issuer,
-        hash_fields.as_slice()
-    );
-    assert!(verified, "Signature was not verified properly!");
-
-    Ok(signature.to_string())
+    sign_hash(signer, root, logger)
 }
 
 pub fn hash_inputs_size_8<N: NetworkNative>(inputs: Vec<&str>) -> Result<Vec<Field<N>>, CustomError> {
@@ -127,92 +110,79 @@ pub fn hash_inputs_size_8<N: NetworkNative>(inputs: Vec<&str>) -> Result<Vec<Fie
             s if s.ends_with("u8") => {
                 let (_, num) = U8::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse U8: {}", e));
-                let value = Value::<N>::try_from(Literal::U8(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert U8 to Value: {}", e));
+                let value = Value::<N>::from(Literal::U8(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("u16") => {
                 let (_, num) = U16::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse U16: {}", e));
-                let value = Value::<N>::try_from(Literal::U16(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert U16 to Value: {}", e));
+                let value = Value::<N>::from(Literal::U16(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("u32") => {
                 let (_, num) = U32::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse U32: {}", e));
-                let value = Value::<N>::try_from(Literal::U32(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert U32 to Value: {}", e));
+                let value = Value::<N>::from(Literal::U32(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("u64") => {
                 let (_, num) = U64::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse U64: {}", e));
-                let value = Value::<N>::try_from(Literal::U64(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert U64 to Value: {}", e));
+                let value = Value::<N>::from(Literal::U64(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("u128") => {
                 let (_, num) = U128::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse U128: {}", e));
-                let value = Value::<N>::try_from(Literal::U128(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert U128 to Value: {}", e));
+                let value = Value::<N>::from(Literal::U128(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("i8") => {
                 let (_, num) = I8::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse I8: {}", e));
-                let value = Value::<N>::try_from(Literal::I8(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert I8 to Value: {}", e));
+                let value = Value::<N>::from(Literal::I8(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("i16") => {
                 let (_, num) = I16::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse I16: {}", e));
-                let value = Value::<N>::try_from(Literal::I16(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert I16 to Value: {}", e));
+                let value = Value::<N>::from(Literal::I16(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("i32") => {
                 let (_, num) = I32::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse I32: {}", e));
-                let value = Value::<N>::try_from(Literal::I32(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert I328 to Value: {}", e));
+                let value = Value::<N>::from(Literal::I32(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("i64") => {
                 let (_, num) = I64::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse I64: {}", e));
-                let value = Value::<N>::try_from(Literal::I64(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert I64 to Value: {}", e));
+                let value = Value::<N>::from(Literal::I64(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("i128") => {
                 let (_, num) = I128::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse I128: {}", e));
-                let value = Value::<N>::try_from(Literal::I128(num))
-                    .unwrap_or_else(|e| panic!("Failed to convert I128 to Value: {}", e));
+                let value = Value::<N>::from(Literal::I128(num));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("scalar") => {
                 let (_, scalar) = Scalar::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse Scalar: {}", e));
-                let value = Value::<N>::try_from(Literal::Scalar(scalar))
-                    .unwrap_or_else(|e| panic!("Failed to convert Scalar to Value: {}", e));
+                let value = Value::<N>::from(Literal::Scalar(scalar));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s.ends_with("group") => {
                 let (_, group) = Group::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse Group: {}", e));
-                let value = Value::<N>::try_from(Literal::Group(group))
-                    .unwrap_or_else(|e| panic!("Failed to convert Group to Value: {}", e));
+                let value = Value::<N>::from(Literal::Group(group));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             s if s == "true" || s == "false" => {
                 let (_, boolean) = Boolean::<N>::parse(s)
                     .unwrap_or_else(|e| panic!("Failed to parse Boolean: {}", e));
-                let value = Value::<N>::try_from(Literal::Boolean(boolean))
-                    .unwrap_or_else(|e| panic!("Failed to convert Boolean to Value: {}", e));
+                let value = Value::<N>::from(Literal::Boolean(boolean));
                 N::hash_psd2(value.to_fields()?.as_slice())?
             }
             _ => {
@@ -266,7 +236,8 @@ mod tests {
         let res = hash_inputs_size_8::<N>(TEST_INPUTS.to_vec()).unwrap();
         let tree = MerkleTree::<N>::new(res.clone()).unwrap();
         let private_key = "APrivateKey1zkp8CZNn3yeCseEtxuVPbDCwSyhGW6yZKUYKfgXmcpoGPWH";
-        let sig = sign_root::<N>(private_key, tree.root().to_string().as_str()).unwrap();
+        let signer = PrivateKeySigner::<N>::from_str(private_key).unwrap();
+        let sig = sign_root::<N>(&signer, tree.root().to_string().as_str(), &StdoutLogger).unwrap();
         println!("Leaves: {:?}", res);
         println!("Root: {}", tree.root());
         println!("Signature: {}", sig);
@@ -0,0 +1,116 @@
+use super::*;
+use bip39::Mnemonic;
+use rand::{rngs::OsRng, RngCore};
+use zeroize::Zeroizing;
+
+/// The word counts a mnemonic can have.
+pub const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+// Domain separator of the account seed derivation.
+const ACCOUNT_DOMAIN: &str = "zPassIssuerAccount";
+
+/// Generates a new random English BIP-39 mnemonic with the given number of words.
+pub fn generate_mnemonic(word_count: usize) -> Result<SecretString, CustomError> {
+    if !MNEMONIC_WORD_COUNTS.contains(&word_count) {
+        return Err(anyhow!("Mnemonic word count must be one of {:?}, got {}", MNEMONIC_WORD_COUNTS, word_count).into());
+    }
+    let mut entropy = Zeroizing::new([0u8; 32]);
+    let entropy = &mut entropy[..word_count / 3 * 4];
+    OsRng.fill_bytes(entropy);
+
+    let mnemonic = Mnemonic::from_entropy(entropy)
+        .map_err(|e| anyhow!("Failed to generate mnemonic: {}", e))?;
+    Ok(SecretString::new(mnemonic.to_string()))
+}
+
+/// Checks that the phrase is an English BIP-39 mnemonic with a valid checksum.
+pub fn validate_mnemonic(phrase: &str) -> Result<(), CustomError> {
+    parse_mnemonic(phrase).map(|_| ())
+}
+
+fn parse_mnemonic(phrase: &str) -> Result<Mnemonic, CustomError> {
+    Ok(Mnemonic::parse_in(bip39::Language::English, phrase)
+        .map_err(|e| anyhow!("Invalid mnemonic: {}", e))?)
+}
+
+/// Derives the issuer account at `index` from a mnemonic and an optional BIP-39 passphrase.
+///
+/// This is a custom zPass derivation, not BIP-32 or SLIP-10, and it does not match the accounts
+/// other Aleo wallets derive from the same phrase: the phrase only restores accounts through this
+/// function. The account seed is the Poseidon2 hash, under a domain separator, of the 64-byte
+/// BIP-39 seed followed by the little-endian index, so every index yields an independent account.
+pub fn derive_issuer_account<N: NetworkNative>(phrase: &str, passphrase: &str, index: u32) -> Result<IssuerAccount<N>, CustomError> {
+    let mnemonic = parse_mnemonic(phrase)?;
+    let bip39_seed = Zeroizing::new(mnemonic.to_seed(passphrase));
+    // Sized up front, so the seed is never left behind in a reallocated buffer.
+    let mut seed = Zeroizing::new(Vec::with_capacity(bip39_seed.len() + 4));
+    seed.extend_from_slice(bip39_seed.as_slice());
+    seed.extend_from_slice(&index.to_le_bytes());
+
+    let account_seed = hash_bytes_to_field::<N>(ACCOUNT_DOMAIN, &seed)
+        .map_err(|e| anyhow!("Failed to derive account seed: {}", e))?;
+    let private_key = PrivateKey::<N>::try_from(account_seed)
+        .map_err(|e| anyhow!("Failed to derive private key: {}", e))?;
+    IssuerAccount::new(SecretPrivateKey::new(private_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_generate_and_validate_mnemonic() {
+        for word_count in MNEMONIC_WORD_COUNTS {
+            let phrase = generate_mnemonic(word_count).unwrap();
+            assert_eq!(phrase.expose().split_whitespace().count(), word_count);
+            assert!(validate_mnemonic(phrase.expose()).is_ok());
+        }
+        assert!(generate_mnemonic(13).is_err());
+
+        assert!(validate_mnemonic(PHRASE).is_ok());
+        assert!(validate_mnemonic(&PHRASE.replace("about", "abandon")).is_err());
+        assert!(validate_mnemonic("not a mnemonic").is_err());
+    }
+
+    #[test]
+    fn test_derive_issuer_account_is_deterministic() {
+        let first = derive_issuer_account::<N>(PHRASE, "", 0).unwrap();
+        let again = derive_issuer_account::<N>(PHRASE, "", 0).unwrap();
+        assert_eq!(first.private_key().expose(), again.private_key().expose());
+        assert_eq!(first.address(), again.address());
+        assert_eq!(first.address(), Address::<N>::try_from(first.private_key().expose()).unwrap());
+
+        let second = derive_issuer_account::<N>(PHRASE, "", 1).unwrap();
+        let protected = derive_issuer_account::<N>(PHRASE, "passphrase", 0).unwrap();
+        assert_ne!(first.address(), second.address());
+        assert_ne!(first.address(), protected.address());
+    }
+
+    #[test]
+    fn test_derive_known_accounts() {
+        // Any change to the derivation changes these, and with them every restored issuer account.
+        let known = [
+            ("", 0, "APrivateKey1zkpFXc2koKD5iGA2eDdjx2KZ6LDSRgohuWdZvqAcCRpAzeJ", "aleo15xzctll2gvhkaendfllr3c6r6grfmkr2uaey852zyvu3sg95fsrsf5ef4e"),
+            ("", 1, "APrivateKey1zkpA2f6zNyxDncCj1asHJG7BoqfCVobJusay5UNuR4TfyWX", "aleo1n2unynpgk5tzxn33r8enjvayu299xawlum497fvyhfezr24u8cgq2s8rxl"),
+            ("passphrase", 0, "APrivateKey1zkpJkKjJH6wbAswJiA7G5YRVH2hZoYB3jHADTJdFrBFSZAo", "aleo1puxg6d6p4vsg0e7jtflytgxmd584f72udnm7pfk2ancsw3j57qyslfe69p"),
+        ];
+        for (passphrase, index, private_key, address) in known {
+            let account = derive_issuer_account::<N>(PHRASE, passphrase, index).unwrap();
+            assert_eq!(account.private_key().expose().to_string(), private_key);
+            assert_eq!(account.address().to_string(), address);
+        }
+    }
+
+    #[test]
+    fn test_derived_account_signs() {
+        let signer = derive_issuer_account::<N>(PHRASE, "", 3).unwrap().into_signer().unwrap();
+        let message = string_to_value_fields::<N>("123field");
+        let signature = signer.sign(&message).unwrap();
+        assert!(verify_signature_with_address_and_message(&signature, &signer.address().unwrap(), &message));
+    }
+}
@@ -0,0 +1,417 @@
+use super::*;
+
+/// The issuers allowed to attest a credential, and how many of them must sign it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct IssuerPolicy<N: NetworkNative> {
+    threshold: usize,
+    issuers: Vec<Address<N>>,
+}
+
+impl<N: NetworkNative> IssuerPolicy<N> {
+    /// Creates a `threshold`-of-`issuers.len()` policy over distinct issuers.
+    pub fn new(threshold: usize, issuers: Vec<Address<N>>) -> Result<Self, CustomError> {
+        let policy = Self { threshold, issuers };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    fn validate(&self) -> Result<(), CustomError> {
+        if self.threshold == 0 || self.threshold > self.issuers.len() {
+            return Err(anyhow!("Threshold must be between 1 and the number of issuers ({}), got {}", self.issuers.len(), self.threshold).into());
+        }
+        if let Some((i, issuer)) = self.issuers.iter().enumerate().find(|(i, issuer)| self.issuers[..*i].contains(issuer)) {
+            return Err(anyhow!("Issuer {} is listed twice (at {})", issuer, i).into());
+        }
+        Ok(())
+    }
+
+    /// Returns the number of listed issuers that must sign.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns the listed issuers.
+    pub fn issuers(&self) -> &[Address<N>] {
+        &self.issuers
+    }
+
+    /// Returns whether the issuer is listed.
+    pub fn contains(&self, issuer: &Address<N>) -> bool {
+        self.issuers.contains(issuer)
+    }
+
+    /// Returns whether both policies have the same threshold and issuers, in any order.
+    pub fn same_as(&self, other: &Self) -> bool {
+        self.threshold == other.threshold
+            && self.issuers.len() == other.issuers.len()
+            && self.issuers.iter().all(|issuer| other.contains(issuer))
+    }
+}
+
+impl<N: NetworkNative> FromStr for IssuerPolicy<N> {
+    type Err = CustomError;
+
+    /// Parses and validates a policy from its JSON encoding.
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        let policy: Self = serde_json::from_str(policy)
+            .map_err(|e| anyhow!("Malformed issuer policy: {}", e))?;
+        policy.validate()?;
+        Ok(policy)
+    }
+}
+
+impl<N: NetworkNative> std::fmt::Display for IssuerPolicy<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+/// One issuer's signature over the credential hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct IssuerSignature<N: NetworkNative> {
+    pub issuer: Address<N>,
+    pub signature: Signature<N>,
+}
+
+/// A credential hash attested by several issuers under an `IssuerPolicy`.
+///
+/// Every issuer signs the same hash from `hash_credential`. A co-signer's signature only verifies
+/// against its own address, so `verify_credential_with_logger` accepts it as a single-issuer
+/// signature only for the co-signer named by the `issuer` attribute; the others fail with
+/// `CustomError::IssuerMismatch`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MultiIssuerCredential<N: NetworkNative> {
+    network: u16,
+    hash: String,
+    policy: IssuerPolicy<N>,
+    signatures: Vec<IssuerSignature<N>>,
+}
+
+impl<N: NetworkNative> MultiIssuerCredential<N> {
+    /// Starts collecting signatures over the credential hash.
+    pub fn new(hash: &str, policy: IssuerPolicy<N>) -> Result<Self, CustomError> {
+        let credential = Self { network: N::ID, hash: hash.to_string(), policy, signatures: Vec::new() };
+        credential.hash_fields()?;
+        Ok(credential)
+    }
+
+    /// Starts collecting signatures over the hash of the attributes in the message, computed exactly
+    /// as `hash_credential` does, after running `checks` on them for the policy's issuers.
+    ///
+    /// The issuer attribute, if any, cannot be every co-signer's address, so instead of matching
+    /// the signing key it must name one of the listed issuers.
+    pub fn from_message(message: SignInboundMessage, policy: IssuerPolicy<N>, hash: HashAlgorithm, output: HashOutputType, checks: &SignChecks, logger: &dyn Logger) -> Result<Self, CustomError> {
+        Self::check_message(&message, &policy, checks)?;
+        let hash = hash_credential::<N>(message, hash, output, logger)?;
+        Self::new(&hash, policy)
+    }
+
+    /// Returns the credential hash.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Returns the policy.
+    pub fn policy(&self) -> &IssuerPolicy<N> {
+        &self.policy
+    }
+
+    /// Returns the collected signatures.
+    pub fn signatures(&self) -> &[IssuerSignature<N>] {
+        &self.signatures
+    }
+
+    fn hash_fields(&self) -> Result<Vec<Field<N>>, CustomError> {
+        let hash = Value::<N>::from_str(&self.hash)
+            .map_err(|e| anyhow!("Invalid credential hash '{}': {}", self.hash, e))?;
+        Ok(hash.to_fields()?)
+    }
+
+    /// Signs the hash with the signer, which must be a listed issuer.
+    pub fn sign(&mut self, signer: &dyn CredentialSigner<N>) -> Result<(), CustomError> {
+        let issuer = signer.address()?;
+        let signature = signer.sign(&self.hash_fields()?)?;
+        self.add_signature(issuer, signature)
+    }
+
+    /// Signs the hash like `sign`, after running `checks` on the attributes in the message as
+    /// `from_message` does and checking that the hash is theirs.
+    pub fn sign_message(&mut self, signer: &dyn CredentialSigner<N>, message: SignInboundMessage, hash: HashAlgorithm, output: HashOutputType, checks: &SignChecks, logger: &dyn Logger) -> Result<(), CustomError> {
+        Self::check_message(&message, &self.policy, checks)?;
+        let hash = hash_credential::<N>(message, hash, output, logger)?;
+        if hash != self.hash {
+            return Err(anyhow!("The credential hash {} is not the hash {} of the attributes", self.hash, hash).into());
+        }
+        self.sign(signer)
+    }
+
+    fn check_message(message: &SignInboundMessage, policy: &IssuerPolicy<N>, checks: &SignChecks) -> Result<(), CustomError> {
+        let co_signer_checks = SignChecks { issuer_matches_key: false, ..checks.clone() };
+        co_signer_checks.check(&message.data, &policy.issuers[0])?;
+        if checks.issuer_matches_key {
+            if let Some(value) = message.data.get(ISSUER_ATTRIBUTE) {
+                let listed = value.as_str()
+                    .and_then(|value| Address::<N>::from_str(value).ok())
+                    .is_some_and(|issuer| policy.contains(&issuer));
+                if !listed {
+                    return Err(anyhow!("Issuer attribute {} is not listed in the policy", value).into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a signature collected elsewhere, after checking it is a valid signature of a listed issuer
+    /// that has not signed yet.
+    pub fn add_signature(&mut self, issuer: Address<N>, signature: Signature<N>) -> Result<(), CustomError> {
+        if !self.policy.contains(&issuer) {
+            return Err(anyhow!("Issuer {} is not listed in the policy", issuer).into());
+        }
+        if self.signatures.iter().any(|signed| signed.issuer == issuer) {
+            return Err(anyhow!("Issuer {} has already signed", issuer).into());
+        }
+        if !verify_signature_with_address_and_message(&signature, &issuer, &self.hash_fields()?) {
+            return Err(anyhow!("Signature of issuer {} does not match the credential hash", issuer).into());
+        }
+        self.signatures.push(IssuerSignature { issuer, signature });
+        Ok(())
+    }
+
+    /// Returns the distinct listed issuers whose signature over the hash is valid.
+    pub fn valid_issuers(&self) -> Result<Vec<Address<N>>, CustomError> {
+        let hash_fields = self.hash_fields()?;
+        let mut issuers: Vec<Address<N>> = Vec::new();
+        for IssuerSignature { issuer, signature } in &self.signatures {
+            if self.policy.contains(issuer)
+                && !issuers.contains(issuer)
+                && verify_signature_with_address_and_message(signature, issuer, &hash_fields)
+            {
+                issuers.push(*issuer);
+            }
+        }
+        Ok(issuers)
+    }
+
+    /// Checks that at least `threshold` distinct issuers of the verifier's trusted `policy` signed
+    /// the hash.
+    ///
+    /// The policy embedded in the credential is chosen by whoever assembled it, so it must match the
+    /// trusted one: a credential carrying any other threshold or issuers is rejected.
+    pub fn verify(&self, policy: &IssuerPolicy<N>) -> Result<(), CustomError> {
+        policy.validate()?;
+        if !self.policy.same_as(policy) {
+            return Err(anyhow!("The credential policy does not match the trusted issuer policy").into());
+        }
+        let signed = self.valid_issuers()?.len();
+        if signed < policy.threshold {
+            return Err(anyhow!("{} of the required {} issuers signed the credential", signed, policy.threshold).into());
+        }
+        Ok(())
+    }
+
+    /// Verifies the credential like `verify`, after checking that its hash is the hash of the
+    /// attributes in the message, computed exactly as `hash_credential` does, and, given a
+    /// reference time, that the attributes are inside their validity window at it.
+    pub fn verify_attributes(&self, message: SignInboundMessage, policy: &IssuerPolicy<N>, at: Option<ReferenceTime>, hash: HashAlgorithm, output: HashOutputType, logger: &dyn Logger) -> Result<(), CustomError> {
+        Validity::check_data(&message.data, at)?;
+        let hash = hash_credential::<N>(message, hash, output, logger)?;
+        if hash != self.hash {
+            return Err(anyhow!("The credential hash {} is not the hash {} of the attributes", self.hash, hash).into());
+        }
+        self.verify(policy)
+    }
+}
+
+impl<N: NetworkNative> FromStr for MultiIssuerCredential<N> {
+    type Err = CustomError;
+
+    /// Parses a credential from its JSON encoding. The signatures are checked by `verify`.
+    fn from_str(credential: &str) -> Result<Self, Self::Err> {
+        let credential: Self = serde_json::from_str(credential)
+            .map_err(|e| anyhow!("Malformed multi-issuer credential: {}", e))?;
+        if credential.network != N::ID {
+            return Err(anyhow!("Multi-issuer credential belongs to network {}, expected {}", credential.network, N::ID).into());
+        }
+        credential.policy.validate()?;
+        credential.hash_fields()?;
+        Ok(credential)
+    }
+}
+
+impl<N: NetworkNative> std::fmt::Display for MultiIssuerCredential<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::SUBJECT;
+    use serde_json::json;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    fn signers(count: usize) -> Vec<PrivateKeySigner<N>> {
+        (0..count).map(|_| IssuerAccount::<N>::generate().unwrap().into_signer().unwrap()).collect()
+    }
+
+    fn message() -> SignInboundMessage {
+        SignInboundMessage {
+            data: json!({ "subject": SUBJECT, "dob": "20000101u32" }),
+        }
+    }
+
+    fn policy(threshold: usize, signers: &[PrivateKeySigner<N>]) -> IssuerPolicy<N> {
+        IssuerPolicy::new(threshold, signers.iter().map(|signer| signer.address().unwrap()).collect()).unwrap()
+    }
+
+    fn credential(threshold: usize, signers: &[PrivateKeySigner<N>]) -> MultiIssuerCredential<N> {
+        let hash = hash_credential::<N>(message(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).unwrap();
+        MultiIssuerCredential::new(&hash, policy(threshold, signers)).unwrap()
+    }
+
+    #[test]
+    fn test_threshold_reached() {
+        let signers = signers(3);
+        let mut credential = credential(2, &signers);
+
+        let trusted = policy(2, &signers);
+        credential.sign(&signers[0]).unwrap();
+        assert!(credential.verify(&trusted).is_err());
+        assert!(credential.sign(&signers[0]).is_err());
+
+        credential.sign(&signers[2]).unwrap();
+        assert!(credential.verify(&trusted).is_ok());
+        assert_eq!(credential.valid_issuers().unwrap(), vec![signers[0].address().unwrap(), signers[2].address().unwrap()]);
+
+        let parsed = MultiIssuerCredential::<N>::from_str(&credential.to_string()).unwrap();
+        assert_eq!(parsed, credential);
+        assert!(parsed.verify(&trusted).is_ok());
+        assert!(parsed.verify_attributes(message(), &trusted, None, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).is_ok());
+        assert!(MultiIssuerCredential::<MainnetV0>::from_str(&credential.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_unlisted_and_forged_signatures() {
+        let signers = signers(3);
+        let mut credential = credential(2, &signers[..2]);
+
+        assert!(credential.sign(&signers[2]).is_err());
+
+        let other_hash = string_to_value_fields::<N>("1field");
+        let forged = signers[1].sign(&other_hash).unwrap();
+        assert!(credential.add_signature(signers[1].address().unwrap(), forged).is_err());
+
+        // A signature duplicated in an encoded credential is only counted once.
+        credential.sign(&signers[0]).unwrap();
+        let mut encoded: JsonValue = serde_json::from_str(&credential.to_string()).unwrap();
+        let signature = encoded["signatures"][0].clone();
+        encoded["signatures"].as_array_mut().unwrap().push(signature);
+        let parsed = MultiIssuerCredential::<N>::from_str(&encoded.to_string()).unwrap();
+        assert!(parsed.verify(&policy(2, &signers[..2])).is_err());
+    }
+
+    #[test]
+    fn test_untrusted_policy_and_hash() {
+        let signers = signers(3);
+        let trusted = policy(2, &signers);
+
+        // A credential under a policy of its own making is rejected, even when it meets that policy.
+        let mut forged = credential(1, &signers[2..]);
+        forged.sign(&signers[2]).unwrap();
+        assert!(forged.verify(&policy(1, &signers[2..])).is_ok());
+        assert!(forged.verify(&trusted).is_err());
+
+        let mut credential = credential(2, &signers);
+        credential.sign(&signers[0]).unwrap();
+        credential.sign(&signers[1]).unwrap();
+        let reordered = IssuerPolicy::new(2, signers.iter().rev().map(|signer| signer.address().unwrap()).collect()).unwrap();
+        assert!(credential.verify(&reordered).is_ok());
+        assert!(credential.verify(&policy(3, &signers)).is_err());
+
+        let mut other = message();
+        other.data["dob"] = json!("20000102u32");
+        assert!(credential.verify_attributes(other, &trusted, None, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).is_err());
+        assert!(credential.verify_attributes(message(), &trusted, None, HashAlgorithm::BHP1024, HashOutputType::Field, &StdoutLogger).is_err());
+        assert!(credential.verify_attributes(message(), &trusted, None, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).is_ok());
+    }
+
+    #[test]
+    fn test_validity_window() {
+        let signers = signers(2);
+        let trusted = policy(2, &signers);
+        let mut message = message();
+        message.data["not_before"] = json!("20240101u32");
+        message.data["expiry"] = json!("20241231u32");
+        let hash = hash_credential::<N>(message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).unwrap();
+        let mut credential = MultiIssuerCredential::new(&hash, trusted.clone()).unwrap();
+        credential.sign(&signers[0]).unwrap();
+        credential.sign(&signers[1]).unwrap();
+
+        let verify = |at: u32| credential.verify_attributes(message.clone(), &trusted, Some(ReferenceTime::date(at).unwrap()), HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger);
+        assert!(verify(20240601).is_ok());
+        assert!(matches!(verify(20231231), Err(CustomError::NotYetValid { .. })));
+        assert!(matches!(verify(20250101), Err(CustomError::Expired { .. })));
+    }
+
+    #[test]
+    fn test_sign_message_with_checks() {
+        let signers = signers(3);
+        let trusted = policy(2, &signers[..2]);
+        let checks = SignChecks::default().at(ReferenceTime::date(20240101).unwrap());
+        let mut message = message();
+        message.data["issuer"] = json!(signers[0].address().unwrap().to_string());
+        let sign = |credential: &mut MultiIssuerCredential<N>, signer: &PrivateKeySigner<N>, message: SignInboundMessage| {
+            credential.sign_message(signer, message, HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger)
+        };
+
+        let mut credential = MultiIssuerCredential::from_message(message.clone(), trusted.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger).unwrap();
+        assert_eq!(credential.hash(), hash_credential::<N>(message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).unwrap());
+        sign(&mut credential, &signers[1], message.clone()).unwrap();
+        let mut other = message.clone();
+        other.data["dob"] = json!("20000102u32");
+        assert!(sign(&mut credential, &signers[0], other).is_err());
+        sign(&mut credential, &signers[0], message.clone()).unwrap();
+        assert!(credential.verify_attributes(message.clone(), &trusted, None, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).is_ok());
+
+        // Only the co-signer named by the issuer attribute holds a single-issuer signature.
+        for IssuerSignature { issuer, signature } in credential.signatures() {
+            let verified = verify_credential_with_logger(message.clone(), signature, issuer, HashAlgorithm::POSEIDON2, HashOutputType::Field, None, &StdoutLogger);
+            if *issuer == signers[0].address().unwrap() {
+                assert!(verified.unwrap().0);
+            } else {
+                assert!(matches!(verified, Err(CustomError::IssuerMismatch { .. })));
+            }
+        }
+
+        let mut unlisted = message.clone();
+        unlisted.data["issuer"] = json!(signers[2].address().unwrap().to_string());
+        assert!(MultiIssuerCredential::from_message(unlisted, trusted.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger).is_err());
+        let mut expired = message.clone();
+        expired.data["expiry"] = json!("20231231u32");
+        assert!(matches!(
+            MultiIssuerCredential::from_message(expired, trusted.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger),
+            Err(CustomError::Expired { .. })
+        ));
+        message.data.as_object_mut().unwrap().remove("subject");
+        assert!(matches!(
+            MultiIssuerCredential::from_message(message, trusted, HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger),
+            Err(CustomError::MissingAttribute(_))
+        ));
+    }
+
+    #[test]
+    fn test_policy_validation() {
+        let signers = signers(2);
+        let issuers: Vec<Address<N>> = signers.iter().map(|signer| signer.address().unwrap()).collect();
+        assert!(IssuerPolicy::new(0, issuers.clone()).is_err());
+        assert!(IssuerPolicy::new(3, issuers.clone()).is_err());
+        assert!(IssuerPolicy::new(2, vec![issuers[0], issuers[0]]).is_err());
+        assert!(IssuerPolicy::new(2, issuers).is_ok());
+    }
+}
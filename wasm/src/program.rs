@@ -0,0 +1,301 @@
+use super::*;
+use snarkvm_console::program::{PlaintextType, ProgramID, StructType};
+use snarkvm_synthesizer_program::Program;
+
+/// The struct definitions of a compiled Aleo program, such as `verify_poseidon2_zpass.aleo`.
+///
+/// Credentials built from these definitions follow the program's member order and literal types,
+/// so they hash exactly like the struct the program's transition hashes. The JSON attribute order
+/// does not matter, as attributes are laid out in the order the struct declares them.
+#[derive(Debug, Clone)]
+pub struct ProgramStructs<N: NetworkNative> {
+    id: ProgramID<N>,
+    structs: IndexMap<Identifier<N>, StructType<N>>,
+}
+
+impl<N: NetworkNative> ProgramStructs<N> {
+    /// Parses the `.aleo` program source with the snarkVM program parser and keeps its structs.
+    ///
+    /// The whole program must be valid, so every struct a member refers to is defined and
+    /// credentials can always be built.
+    pub fn from_source(source: &str) -> Result<Self, CustomError> {
+        let program = Program::<N>::from_str(source)
+            .map_err(|e| anyhow!("Failed to parse the program: {}", e))?;
+        Ok(Self { id: *program.id(), structs: program.structs().clone() })
+    }
+
+    /// Returns the program ID, e.g. `verify_poseidon2_zpass.aleo`.
+    pub fn id(&self) -> &ProgramID<N> {
+        &self.id
+    }
+
+    /// Returns the names of the structs the program defines, in definition order.
+    pub fn names(&self) -> Vec<String> {
+        self.structs.keys().map(|name| name.to_string()).collect()
+    }
+
+    /// Returns the definition of the struct `name`.
+    pub fn get(&self, name: &str) -> Result<&StructType<N>, CustomError> {
+        Identifier::<N>::from_str(name)
+            .ok()
+            .and_then(|name| self.structs.get(&name))
+            .ok_or_else(|| anyhow!("Program '{}' does not define a struct '{}'", self.id, name).into())
+    }
+
+    /// Builds the struct `name` out of the credential attributes, type-checking them against the definition.
+    ///
+    /// Attributes must match the struct members exactly. Literals are given as strings such as
+    /// `"20000101u32"`; integers may also be JSON numbers and booleans JSON booleans, as their type
+    /// is known. Nested structs are JSON objects and arrays JSON arrays.
+    pub fn to_plaintext(&self, name: &str, data: &JsonValue) -> Result<Plaintext<N>, CustomError> {
+        let definition = self.get(name)?;
+        self.struct_to_plaintext(definition, data, name)
+    }
+
+    /// Hashes the struct `name` built out of the credential attributes, as the program would hash it.
+    pub fn hash(&self, name: &str, data: &JsonValue, algorithm: HashAlgorithm, output: HashOutputType) -> Result<String, CustomError> {
+        let plaintext = self.to_plaintext(name, data)?;
+        Ok(create_hash(Value::Plaintext(plaintext), algorithm, output)?)
+    }
+
+    fn struct_to_plaintext(&self, definition: &StructType<N>, data: &JsonValue, path: &str) -> Result<Plaintext<N>, CustomError> {
+        let data = data.as_object()
+            .ok_or_else(|| anyhow!("'{}' must be a JSON object for struct '{}'", path, definition.name()))?;
+        if let Some(unknown) = data.keys().find(|key| !definition.members().keys().any(|member| member.to_string() == **key)) {
+            return Err(anyhow!("'{}.{}' is not a member of struct '{}'", path, unknown, definition.name()).into());
+        }
+
+        let mut members = IndexMap::with_capacity(definition.members().len());
+        for (member, member_type) in definition.members() {
+            let member_path = format!("{}.{}", path, member);
+            let value = data.get(&member.to_string())
+                .ok_or_else(|| anyhow!("Missing '{}' of type '{}'", member_path, member_type))?;
+            members.insert(*member, self.value_to_plaintext(member_type, value, &member_path)?);
+        }
+        Ok(Plaintext::Struct(members, Default::default()))
+    }
+
+    fn value_to_plaintext(&self, plaintext_type: &PlaintextType<N>, value: &JsonValue, path: &str) -> Result<Plaintext<N>, CustomError> {
+        match plaintext_type {
+            PlaintextType::Literal(literal_type) => Ok(Plaintext::from(literal_from_json(*literal_type, value, path)?)),
+            PlaintextType::Struct(name) => {
+                let definition = self.structs.get(name)
+                    .ok_or_else(|| anyhow!("Struct '{}' is used but not defined", name))?;
+                self.struct_to_plaintext(definition, value, path)
+            }
+            PlaintextType::Array(array_type) => {
+                let elements = value.as_array()
+                    .filter(|elements| elements.len() == **array_type.length() as usize)
+                    .ok_or_else(|| anyhow!("'{}' must be a JSON array of {} elements", path, array_type.length()))?;
+                let elements = elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, element)| self.value_to_plaintext(array_type.next_element_type(), element, &format!("{}[{}]", path, i)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Plaintext::Array(elements, Default::default()))
+            }
+        }
+    }
+}
+
+/// Converts the JSON attribute at `path` into a literal of the given type.
+///
+/// Literals are given as strings such as `"20000101u32"`, or plain strings for fields as in
+/// `convert_data_to_struct`. Integers may also be JSON numbers and booleans JSON booleans.
+pub(crate) fn literal_from_json<N: NetworkNative>(literal_type: LiteralType, value: &JsonValue, path: &str) -> Result<Literal<N>, CustomError> {
+    let literal = match value {
+        JsonValue::String(s) => Literal::<N>::from_str(s).or_else(|e| match literal_type {
+            LiteralType::Field if !s.ends_with("field") => Ok(Literal::Field(string_to_field::<N>(Some(s.clone()))?)),
+            _ => Err(e),
+        }),
+        JsonValue::Number(n) => Literal::<N>::from_str(&format!("{}{}", n, literal_type)),
+        JsonValue::Bool(b) => Literal::<N>::from_str(&b.to_string()),
+        _ => Err(anyhow!("not a literal")),
+    }.map_err(|e| anyhow!("'{}' must be a {} literal, got {}: {}", path, literal_type, value, e))?;
+
+    if literal.to_type() != literal_type {
+        return Err(anyhow!("'{}' must be a {} literal, got a {} '{}'", path, literal_type, literal.to_type(), literal).into());
+    }
+    Ok(literal)
+}
+
+/// Signs the struct `name` of the program, built out of the credential attributes in the message.
+///
+/// Behaves like `sign_message_with_checks`, except the credential is type-checked against the
+/// program's struct definition and hashed in its member order, as the program's transition does.
+///
+/// # Returns
+///
+/// A result with tuple of signature and hash as strings if successful, otherwise returns a `CustomError`.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_program_struct<N: NetworkNative>(
+    signer: &dyn CredentialSigner<N>,
+    program: &ProgramStructs<N>,
+    name: &str,
+    message: SignInboundMessage,
+    hash: HashAlgorithm,
+    output: HashOutputType,
+    checks: &SignChecks,
+    logger: &dyn Logger,
+) -> Result<(String, String), CustomError> {
+    checks.check(&message.data, &signer.address()?)?;
+
+    logger.log(&format!("Program struct: {}/{}", program.id(), name));
+
+    let hash = program.hash(name, &message.data, hash, output)?;
+    let signature = sign_hash(signer, &hash, logger)?;
+
+    Ok((signature, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, ISSUER, SUBJECT};
+    use serde_json::json;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    // The struct definitions of the compiled `verify_poseidon2_zpass` program.
+    const PROGRAM: &str = r"
+import credits.aleo;
+program verify_poseidon2_zpass.aleo;
+
+record ZPass:
+    owner as address.private;
+    issuer as address.private;
+    dob as u32.private;
+    nationality as field.private;
+    expiry as u32.private;
+    salt as scalar.private;
+
+struct PrivateCredentials:
+    issuer as address;
+    subject as address;
+    dob as u32;
+    nationality as field;
+    expiry as u32;
+
+struct PublicCredentials:
+    salt as scalar;
+
+struct FullCredentials:
+    issuer as address;
+    subject as address;
+    dob as u32;
+    nationality as field;
+    expiry as u32;
+    salt as scalar;
+
+mapping issued:
+    key as group.public;
+    value as boolean.public;
+";
+
+    fn credentials() -> JsonValue {
+        json!({
+            "salt": "1234scalar",
+            "expiry": "20300101u32",
+            "nationality": "840field",
+            "dob": 20000101,
+            "subject": SUBJECT,
+            "issuer": ISSUER,
+        })
+    }
+
+    #[test]
+    fn test_parse_program_structs() {
+        let program = ProgramStructs::<N>::from_source(PROGRAM).unwrap();
+        assert_eq!(program.id().to_string(), "verify_poseidon2_zpass.aleo");
+        assert_eq!(program.names(), vec!["PrivateCredentials", "PublicCredentials", "FullCredentials"]);
+        assert_eq!(program.get("FullCredentials").unwrap().members().len(), 6);
+        assert!(program.get("ZPass").is_err());
+
+        assert!(ProgramStructs::<N>::from_source("struct A:\n    a as u8;\n").is_err());
+        assert!(ProgramStructs::<N>::from_source("program p.aleo;\nstruct A:\n    b as B;\n").is_err());
+        assert!(ProgramStructs::<N>::from_source("program p.aleo;\nstruct A:\n    a as u8;\nstruct A:\n    a as u8;\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_whole_program() {
+        let source = "// Holders.\nprogram p.aleo; struct A: a as u8; // The age.\n  struct B:\n    a as A;\n\nfunction f:\n    input r0 as B.private;\n    output r0.a as A.private;\n";
+        let program = ProgramStructs::<N>::from_source(source).unwrap();
+        assert_eq!(program.names(), vec!["A", "B"]);
+        assert!(program.to_plaintext("B", &json!({ "a": { "a": "7u8" } })).is_ok());
+
+        // The rest of the program must be valid too.
+        assert!(ProgramStructs::<N>::from_source(&format!("{}function g:\n    input r0 u8.private;\n", source)).is_err());
+    }
+
+    #[test]
+    fn test_hash_matches_program_struct() {
+        let program = ProgramStructs::<N>::from_source(PROGRAM).unwrap();
+        let hash = program.hash("FullCredentials", &credentials(), HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap();
+
+        // The struct the transition builds, in its member order.
+        let expected = Plaintext::<N>::from_str(&format!(
+            "{{ issuer: {}, subject: {}, dob: 20000101u32, nationality: 840field, expiry: 20300101u32, salt: 1234scalar }}",
+            ISSUER, SUBJECT
+        )).unwrap();
+        let expected = create_hash(Value::Plaintext(expected), HashAlgorithm::POSEIDON2, HashOutputType::Field).unwrap();
+        assert_eq!(hash, expected);
+
+        // The attribute-order hash of the same credential does not match the program.
+        let mut data = credentials();
+        data["dob"] = json!("20000101u32");
+        let attribute_order = hash_credential::<N>(SignInboundMessage { data }, HashAlgorithm::POSEIDON2, HashOutputType::Field, &StdoutLogger).unwrap();
+        assert_ne!(hash, attribute_order);
+    }
+
+    #[test]
+    fn test_type_checks_credentials() {
+        let program = ProgramStructs::<N>::from_source(PROGRAM).unwrap();
+        let check = |data: JsonValue| program.to_plaintext("FullCredentials", &data);
+
+        let mut missing = credentials();
+        missing.as_object_mut().unwrap().remove("salt");
+        assert!(check(missing).is_err());
+
+        let mut extra = credentials();
+        extra["name"] = json!("Alice");
+        assert!(check(extra).is_err());
+
+        let mut wrong_type = credentials();
+        wrong_type["dob"] = json!("20000101u64");
+        assert!(check(wrong_type).is_err());
+
+        let mut out_of_range = credentials();
+        out_of_range["expiry"] = json!(-1);
+        assert!(check(out_of_range).is_err());
+
+        assert!(check(credentials()).is_ok());
+        assert!(program.to_plaintext("FullCredentials", &json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn test_nested_structs_and_arrays() {
+        let source = "program nested.aleo;\n\nstruct Name:\n    parts as [field; 2u32];\n\nstruct Holder:\n    name as Name;\n    adult as boolean;\n";
+        let program = ProgramStructs::<N>::from_source(source).unwrap();
+
+        let plaintext = program.to_plaintext("Holder", &json!({ "adult": true, "name": { "parts": ["1field", "Alice"] } })).unwrap();
+        let alice = string_to_field::<N>(Some("Alice".to_string())).unwrap();
+        assert_eq!(plaintext.to_string(), Plaintext::<N>::from_str(&format!("{{ name: {{ parts: [1field, {}] }}, adult: true }}", alice)).unwrap().to_string());
+
+        assert!(program.to_plaintext("Holder", &json!({ "adult": true, "name": { "parts": ["1field"] } })).is_err());
+        assert!(program.to_plaintext("Holder", &json!({ "adult": "yes", "name": { "parts": ["1field", "2field"] } })).is_err());
+    }
+
+    #[test]
+    fn test_sign_program_struct() {
+        let program = ProgramStructs::<N>::from_source(PROGRAM).unwrap();
+        let signer = PrivateKeySigner::<N>::from_str(PRIVATE_KEY).unwrap();
+        let message = SignInboundMessage { data: credentials() };
+        let checks = SignChecks::default().at(ReferenceTime::date(20250101).unwrap());
+        let (signature, hash) = sign_program_struct(&signer, &program, "FullCredentials", message, HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger).unwrap();
+
+        let signature = Signature::<N>::from_str(&signature).unwrap();
+        let issuer = Address::<N>::from_str(ISSUER).unwrap();
+        assert!(signature.verify(&issuer, &Value::<N>::from_str(&hash).unwrap().to_fields().unwrap()));
+    }
+}
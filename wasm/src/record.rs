@@ -0,0 +1,261 @@
+use super::*;
+use snarkvm_console::program::{Entry, Record};
+
+/// The layout of a `ZPass` record, by program variant.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ZPassVariant {
+    /// `verify_poseidon2_zpass` and `zpass_invalidate`: the attributes in the clear.
+    Plain = 0,
+    /// `zpass_hiding` and `zpass_invalidate_hiding`: the attributes as commitments.
+    Hiding = 1,
+    /// `zpass_merkle_8`: the merkle root of the attributes.
+    Merkle = 2,
+}
+
+/// The attributes of a `ZPass` record, by program variant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "variant", rename_all = "lowercase", bound = "")]
+pub enum ZPassAttributes<N: NetworkNative> {
+    Plain { issuer: Address<N>, dob: u32, nationality: Field<N>, expiry: u32, salt: Scalar<N> },
+    Hiding { issuer: Group<N>, dob: Group<N>, nationality: Group<N>, expiry: Group<N>, salt: Scalar<N> },
+    Merkle { issuer: Address<N>, root: Field<N> },
+}
+
+/// A decrypted `ZPass` record, as returned by `getZPassRecord`, with typed attributes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ZPassRecord<N: NetworkNative> {
+    owner: Address<N>,
+    #[serde(flatten)]
+    attributes: ZPassAttributes<N>,
+    nonce: Group<N>,
+}
+
+impl<N: NetworkNative> ZPassRecord<N> {
+    /// Parses the record plaintext, e.g. `{ owner: aleo1....private, issuer: ..., _nonce: ...group.public }`,
+    /// as a record of the given variant. Visibilities are dropped, and the record must have exactly
+    /// the entries of the variant, of their types.
+    pub fn parse_variant(record: &str, variant: ZPassVariant) -> Result<Self, CustomError> {
+        Self::from_record(&parse_record(record)?, variant)
+    }
+
+    fn from_record(record: &Record<N, Plaintext<N>>, variant: ZPassVariant) -> Result<Self, CustomError> {
+        let entries = RecordEntries(record.data());
+
+        let attributes = match variant {
+            ZPassVariant::Plain => {
+                entries.expect_names(&["issuer", "dob", "nationality", "expiry", "salt"])?;
+                ZPassAttributes::Plain {
+                    issuer: entries.address("issuer")?,
+                    dob: entries.u32("dob")?,
+                    nationality: entries.field("nationality")?,
+                    expiry: entries.u32("expiry")?,
+                    salt: entries.scalar("salt")?,
+                }
+            }
+            ZPassVariant::Hiding => {
+                entries.expect_names(&["issuer", "dob", "nationality", "expiry", "salt"])?;
+                ZPassAttributes::Hiding {
+                    issuer: entries.group("issuer")?,
+                    dob: entries.group("dob")?,
+                    nationality: entries.group("nationality")?,
+                    expiry: entries.group("expiry")?,
+                    salt: entries.scalar("salt")?,
+                }
+            }
+            ZPassVariant::Merkle => {
+                entries.expect_names(&["issuer", "root"])?;
+                ZPassAttributes::Merkle { issuer: entries.address("issuer")?, root: entries.field("root")? }
+            }
+        };
+        Ok(Self { owner: **record.owner(), attributes, nonce: *record.nonce() })
+    }
+
+    /// Returns the variant of the record.
+    pub fn variant(&self) -> ZPassVariant {
+        match self.attributes {
+            ZPassAttributes::Plain { .. } => ZPassVariant::Plain,
+            ZPassAttributes::Hiding { .. } => ZPassVariant::Hiding,
+            ZPassAttributes::Merkle { .. } => ZPassVariant::Merkle,
+        }
+    }
+
+    /// Returns the owner, i.e. the subject of the credential.
+    pub fn owner(&self) -> &Address<N> {
+        &self.owner
+    }
+
+    /// Returns the attributes.
+    pub fn attributes(&self) -> &ZPassAttributes<N> {
+        &self.attributes
+    }
+
+    /// Returns the record nonce.
+    pub fn nonce(&self) -> &Group<N> {
+        &self.nonce
+    }
+
+    /// Returns whether the record was issued by `issuer`. Hiding records only store a commitment to the
+    /// issuer, which is opened with the record salt.
+    pub fn is_issued_by(&self, issuer: &Address<N>) -> Result<bool, CustomError> {
+        match &self.attributes {
+            ZPassAttributes::Plain { issuer: record_issuer, .. } | ZPassAttributes::Merkle { issuer: record_issuer, .. } => Ok(record_issuer == issuer),
+            ZPassAttributes::Hiding { issuer: commitment, salt, .. } => verify_opening(commitment, &Literal::Address(*issuer), salt),
+        }
+    }
+
+    /// Returns the record as a JSON object, with the `variant` and the typed attributes.
+    pub fn to_json(&self) -> Result<String, CustomError> {
+        Ok(serde_json::to_string(self).map_err(|e| anyhow!("Failed to serialize record: {}", e))?)
+    }
+}
+
+impl<N: NetworkNative> FromStr for ZPassRecord<N> {
+    type Err = CustomError;
+
+    /// Parses the record plaintext, detecting its variant from its entries.
+    fn from_str(record: &str) -> Result<Self, Self::Err> {
+        let record = parse_record::<N>(record)?;
+        let entries = RecordEntries(record.data());
+        let variant = match entries.literal("root") {
+            Ok(_) => ZPassVariant::Merkle,
+            Err(_) if matches!(entries.literal("issuer"), Ok(Literal::Group(_))) => ZPassVariant::Hiding,
+            Err(_) => ZPassVariant::Plain,
+        };
+        Self::from_record(&record, variant)
+    }
+}
+
+fn parse_record<N: NetworkNative>(record: &str) -> Result<Record<N, Plaintext<N>>, CustomError> {
+    Ok(Record::<N, Plaintext<N>>::from_str(record.trim()).map_err(|e| anyhow!("Invalid record plaintext: {}", e))?)
+}
+
+// The entries of a record, with typed accessors.
+struct RecordEntries<'a, N: NetworkNative>(&'a IndexMap<Identifier<N>, Entry<N, Plaintext<N>>>);
+
+impl<N: NetworkNative> RecordEntries<'_, N> {
+    fn expect_names(&self, names: &[&str]) -> Result<(), CustomError> {
+        let found: Vec<String> = self.0.keys().map(|name| name.to_string()).collect();
+        if found != names {
+            return Err(anyhow!("Expected the record entries {}, found {}", names.join(", "), found.join(", ")).into());
+        }
+        Ok(())
+    }
+
+    fn literal(&self, name: &str) -> Result<&Literal<N>, CustomError> {
+        let entry = Identifier::<N>::from_str(name).ok()
+            .and_then(|identifier| self.0.get(&identifier))
+            .ok_or_else(|| anyhow!("Missing record entry '{}'", name))?;
+        match entry {
+            Entry::Constant(Plaintext::Literal(literal, _))
+            | Entry::Public(Plaintext::Literal(literal, _))
+            | Entry::Private(Plaintext::Literal(literal, _)) => Ok(literal),
+            _ => Err(anyhow!("Record entry '{}' must be a literal", name).into()),
+        }
+    }
+
+    fn address(&self, name: &str) -> Result<Address<N>, CustomError> {
+        match self.literal(name)? {
+            Literal::Address(address) => Ok(*address),
+            literal => Err(anyhow!("Record entry '{}' must be an address, got '{}'", name, literal).into()),
+        }
+    }
+
+    fn u32(&self, name: &str) -> Result<u32, CustomError> {
+        match self.literal(name)? {
+            Literal::U32(value) => Ok(**value),
+            literal => Err(anyhow!("Record entry '{}' must be a u32, got '{}'", name, literal).into()),
+        }
+    }
+
+    fn field(&self, name: &str) -> Result<Field<N>, CustomError> {
+        match self.literal(name)? {
+            Literal::Field(field) => Ok(*field),
+            literal => Err(anyhow!("Record entry '{}' must be a field, got '{}'", name, literal).into()),
+        }
+    }
+
+    fn group(&self, name: &str) -> Result<Group<N>, CustomError> {
+        match self.literal(name)? {
+            Literal::Group(group) => Ok(*group),
+            literal => Err(anyhow!("Record entry '{}' must be a group, got '{}'", name, literal).into()),
+        }
+    }
+
+    fn scalar(&self, name: &str) -> Result<Scalar<N>, CustomError> {
+        match self.literal(name)? {
+            Literal::Scalar(scalar) => Ok(*scalar),
+            literal => Err(anyhow!("Record entry '{}' must be a scalar, got '{}'", name, literal).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ISSUER, SUBJECT, credentials};
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    fn plain_record() -> String {
+        format!(
+            "{{\n  owner: {}.private,\n  issuer: {}.private,\n  dob: 20000101u32.private,\n  nationality: 840field.private,\n  expiry: 20300101u32.private,\n  salt: 1234scalar.private,\n  _nonce: 0group.public\n}}",
+            SUBJECT, ISSUER
+        )
+    }
+
+    #[test]
+    fn test_parse_plain_record() {
+        let record = ZPassRecord::<N>::from_str(&plain_record()).unwrap();
+        assert_eq!(record.variant(), ZPassVariant::Plain);
+        assert_eq!(record.owner().to_string(), SUBJECT);
+        assert_eq!(record.attributes(), &ZPassAttributes::Plain {
+            issuer: Address::from_str(ISSUER).unwrap(),
+            dob: 20000101,
+            nationality: Field::from_u16(840),
+            expiry: 20300101,
+            salt: Scalar::from_str("1234scalar").unwrap(),
+        });
+        assert!(record.is_issued_by(&Address::from_str(ISSUER).unwrap()).unwrap());
+        assert!(!record.is_issued_by(&Address::from_str(SUBJECT).unwrap()).unwrap());
+
+        let json: JsonValue = serde_json::from_str(&record.to_json().unwrap()).unwrap();
+        assert_eq!(json["variant"], "plain");
+        assert_eq!(json["owner"], SUBJECT);
+        assert_eq!(json["issuer"], ISSUER);
+        assert_eq!(json["dob"], 20000101);
+        assert_eq!(json["salt"], "1234scalar");
+    }
+
+    #[test]
+    fn test_parse_hiding_and_merkle_records() {
+        let salt = Scalar::<N>::from_str("1234scalar").unwrap();
+        let commitments = hiding_commitments::<N>(&credentials(), &salt).unwrap();
+        let hiding = format!(
+            "{{ owner: {}.private, issuer: {}.private, dob: {}.private, nationality: {}.private, expiry: {}.private, salt: 1234scalar.private, _nonce: 0group.public }}",
+            SUBJECT, commitments["issuer"], commitments["dob"], commitments["nationality"], commitments["expiry"]
+        );
+        let record = ZPassRecord::<N>::from_str(&hiding).unwrap();
+        assert_eq!(record.variant(), ZPassVariant::Hiding);
+        assert!(record.is_issued_by(&Address::from_str(ISSUER).unwrap()).unwrap());
+        assert!(!record.is_issued_by(&Address::from_str(SUBJECT).unwrap()).unwrap());
+
+        let merkle = format!("{{ owner: {}.private, issuer: {}.private, root: 42field.private, _nonce: 0group.public }}", SUBJECT, ISSUER);
+        let record = ZPassRecord::<N>::from_str(&merkle).unwrap();
+        assert_eq!(record.attributes(), &ZPassAttributes::Merkle { issuer: Address::from_str(ISSUER).unwrap(), root: Field::from_u8(42) });
+        assert!(ZPassRecord::<N>::parse_variant(&merkle, ZPassVariant::Plain).is_err());
+    }
+
+    #[test]
+    fn test_invalid_records() {
+        assert!(ZPassRecord::<N>::parse_variant(&plain_record(), ZPassVariant::Hiding).is_err());
+        assert!(ZPassRecord::<N>::from_str(&plain_record().replace("20000101u32", "20000101u64")).is_err());
+        assert!(ZPassRecord::<N>::from_str(&plain_record().replace(ISSUER, "aleo1invalid")).is_err());
+        assert!(ZPassRecord::<N>::from_str(&plain_record().replace(SUBJECT, "aleo1invalid")).is_err());
+        assert!(ZPassRecord::<N>::from_str(&plain_record().replace("  salt: 1234scalar.private,\n", "")).is_err());
+        assert!(ZPassRecord::<N>::from_str("not a record").is_err());
+    }
+}
@@ -0,0 +1,405 @@
+use super::*;
+
+/// The outcome of one verification check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    Skipped,
+}
+
+/// The outcome of one verification check, with the reason for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub status: CheckStatus,
+    pub reason: String,
+}
+
+impl CheckResult {
+    pub fn pass(reason: impl Into<String>) -> Self {
+        Self { status: CheckStatus::Pass, reason: reason.into() }
+    }
+
+    pub fn fail(reason: impl Into<String>) -> Self {
+        Self { status: CheckStatus::Fail, reason: reason.into() }
+    }
+
+    pub fn skipped(reason: impl Into<String>) -> Self {
+        Self { status: CheckStatus::Skipped, reason: reason.into() }
+    }
+}
+
+/// A set of revoked credentials, identified by their hash.
+#[derive(Debug, Clone)]
+pub enum Revocation<N: NetworkNative> {
+    /// The revoked credential hashes.
+    List(Vec<String>),
+    /// The root of a `MerkleTree` whose leaves are the revoked credential hashes, and the proof
+    /// of the checked credential, as looked up in the revocation registry.
+    Tree { root: Field<N>, proof: Vec<Field<N>> },
+}
+
+impl<N: NetworkNative> Revocation<N> {
+    fn check(&self, hash: &str) -> CheckResult {
+        match self {
+            Revocation::List(revoked) if revoked.iter().any(|revoked| revoked == hash) => CheckResult::fail(format!("Credential {} is revoked", hash)),
+            Revocation::List(_) => CheckResult::pass(format!("Credential {} is not revoked", hash)),
+            Revocation::Tree { root, proof } => match Field::<N>::from_str(hash).map(|leaf| MerkleTree::root_of_proof(leaf, proof)) {
+                Err(_) => CheckResult::fail(format!("Credential hash {} is not a field, so it cannot be a revocation tree leaf", hash)),
+                Ok(Err(err)) => CheckResult::fail(err.to_string()),
+                Ok(Ok(proven)) if proven == *root => CheckResult::fail(format!("Credential {} is revoked in the tree {}", hash, root)),
+                Ok(Ok(_)) => CheckResult::pass(format!("The proof does not place credential {} in the revocation tree {}", hash, root)),
+            },
+        }
+    }
+}
+
+/// JSON form of `Revocation::Tree`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RevocationTreeInput {
+    root: String,
+    proof: Vec<String>,
+}
+
+/// The optional checks of `verify_credential_report`. A check without its input is skipped.
+#[derive(Debug, Clone)]
+pub struct VerificationOptions<N: NetworkNative> {
+    /// The issuers the verifier trusts.
+    pub trusted_issuers: Option<Vec<Address<N>>>,
+    /// The exact attributes and types the credential must have.
+    pub schema: Option<IndexMap<String, LiteralType>>,
+    /// The date or block height the validity window is checked at.
+    pub reference_time: Option<ReferenceTime>,
+    /// The revoked credentials.
+    pub revocation: Option<Revocation<N>>,
+    /// The address presenting the credential, which must be its subject.
+    pub holder: Option<Address<N>>,
+}
+
+impl<N: NetworkNative> Default for VerificationOptions<N> {
+    fn default() -> Self {
+        Self { trusted_issuers: None, schema: None, reference_time: None, revocation: None, holder: None }
+    }
+}
+
+/// JSON form of `VerificationOptions`, as passed from JavaScript.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+struct VerificationOptionsInput {
+    trusted_issuers: Option<Vec<String>>,
+    schema: Option<std::collections::BTreeMap<String, String>>,
+    current_time: Option<u32>,
+    time_basis: Option<TimeBasis>,
+    revoked: Option<Vec<String>>,
+    revocation_tree: Option<RevocationTreeInput>,
+    holder: Option<String>,
+}
+
+impl<N: NetworkNative> VerificationOptions<N> {
+    /// Parses the options from their JSON form, e.g.
+    /// `{ "trustedIssuers": ["aleo1..."], "schema": { "dob": "u32" }, "currentTime": 20250101,
+    /// "revoked": ["123field"], "holder": "aleo1..." }`. The current time is a `YYYYMMDD` date
+    /// unless `"timeBasis": "block_height"` is given. Instead of `revoked`, a revocation tree is
+    /// given as `"revocationTree": { "root": "123field", "proof": ["456field", ...] }`.
+    pub fn from_json(options: JsonValue) -> Result<Self, CustomError> {
+        let input: VerificationOptionsInput = serde_json::from_value(options)
+            .map_err(|e| anyhow!("Malformed verification options: {}", e))?;
+
+        let trusted_issuers = input.trusted_issuers
+            .map(|issuers| issuers.iter().map(|issuer| parse_address_normalized::<N>(issuer)).collect::<Result<Vec<_>, _>>())
+            .transpose()?;
+        let schema = input.schema
+            .map(|schema| schema
+                .into_iter()
+                .map(|(name, literal_type)| LiteralType::from_str(&literal_type)
+                    .map(|literal_type| (name, literal_type))
+                    .map_err(|e| anyhow!("Invalid schema type '{}': {}", literal_type, e)))
+                .collect::<Result<IndexMap<_, _>, _>>())
+            .transpose()?;
+        let field = |field: &str| Field::<N>::from_str(field.trim()).map_err(|e| anyhow!("Invalid revocation tree field '{}': {}", field, e));
+        let revocation = match (input.revoked, input.revocation_tree) {
+            (Some(_), Some(_)) => return Err(anyhow!("Give either revoked hashes or a revocation tree, not both").into()),
+            (Some(hashes), None) => Some(Revocation::List(hashes.iter().map(|hash| hash.trim().to_string()).collect())),
+            (None, Some(tree)) => Some(Revocation::Tree {
+                root: field(&tree.root)?,
+                proof: tree.proof.iter().map(|sibling| field(sibling)).collect::<Result<_, _>>()?,
+            }),
+            (None, None) => None,
+        };
+        let reference_time = match (input.current_time, input.time_basis) {
+            (Some(value), basis) => Some(ReferenceTime::new(basis.unwrap_or(TimeBasis::Date), value).map_err(|e| anyhow!(e))?),
+            (None, Some(_)) => return Err(anyhow!("A time basis was given without a current time").into()),
+            (None, None) => None,
+        };
+        let holder = input.holder.map(|holder| parse_address_normalized::<N>(&holder)).transpose()?;
+
+        Ok(Self { trusted_issuers, schema, reference_time, revocation, holder })
+    }
+}
+
+/// The per-check outcome of a credential verification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// Whether every check passed or was skipped, and the signature passed.
+    pub valid: bool,
+    /// The recomputed credential hash, if the credential could be hashed.
+    pub hash: Option<String>,
+    pub signature: CheckResult,
+    pub issuer_trusted: CheckResult,
+    pub schema: CheckResult,
+    pub expiry: CheckResult,
+    pub revocation: CheckResult,
+    pub holder_binding: CheckResult,
+}
+
+impl VerificationReport {
+    /// Returns the checks by name, in report order.
+    pub fn checks(&self) -> [(&'static str, &CheckResult); 6] {
+        [
+            ("signature", &self.signature),
+            ("issuer_trusted", &self.issuer_trusted),
+            ("schema", &self.schema),
+            ("expiry", &self.expiry),
+            ("revocation", &self.revocation),
+            ("holder_binding", &self.holder_binding),
+        ]
+    }
+
+    /// Serializes the report to JSON.
+    pub fn to_json(&self) -> Result<String, CustomError> {
+        Ok(serde_json::to_string(self).map_err(|e| anyhow!("Failed to serialize verification report: {}", e))?)
+    }
+}
+
+/// Verifies the credential like `verify_credential_with_logger`, and runs the optional checks of `options`.
+///
+/// Failures are reported per check rather than returned as errors, so a single report explains
+/// everything that is wrong with the credential. The validity window is reported by the `expiry` check.
+pub fn verify_credential_report<N: NetworkNative>(
+    message: SignInboundMessage,
+    signature: &Signature<N>,
+    issuer: &Address<N>,
+    hash: HashAlgorithm,
+    output: HashOutputType,
+    options: &VerificationOptions<N>,
+    logger: &dyn Logger
+) -> VerificationReport {
+    let data = message.data;
+    let attributes = convert_data_to_struct::<N>(data.clone(), logger);
+
+    // The signature check of `verify_credential_with_logger`, hashing the attributes converted above.
+    let signed = SignChecks::verification().check(&data, issuer).and_then(|_| {
+        let attributes = attributes.as_ref().map_err(|e| anyhow!("{}", e))?;
+        let hash = hash_credential_struct::<N>(attributes.clone(), hash, output, logger)?;
+        Ok((verify_hash(signature, issuer, &hash)?, hash))
+    });
+    let (signature, hash) = match signed {
+        Ok((true, hash)) => (CheckResult::pass("Signature matches the issuer and the recomputed hash"), Some(hash)),
+        Ok((false, hash)) => (CheckResult::fail("Signature does not match the issuer and the recomputed hash"), Some(hash)),
+        Err(err) => (CheckResult::fail(err.to_string()), None),
+    };
+
+    let issuer_trusted = match &options.trusted_issuers {
+        None => CheckResult::skipped("No trusted issuers were given"),
+        Some(trusted) if trusted.contains(issuer) => CheckResult::pass(format!("Issuer {} is trusted", issuer)),
+        Some(_) => CheckResult::fail(format!("Issuer {} is not trusted", issuer)),
+    };
+
+    let schema = match (&options.schema, &attributes) {
+        (None, _) => CheckResult::skipped("No schema was given"),
+        (Some(_), Err(err)) => CheckResult::fail(err.to_string()),
+        (Some(schema), Ok(attributes)) => check_schema(schema, attributes),
+    };
+
+    let expiry = match options.reference_time {
+        None => CheckResult::skipped("No reference time was given"),
+        Some(time) => match Validity::from_data(&data, time.basis).and_then(|validity| validity.check(time.value).map(|_| validity)) {
+            Ok(validity) if validity == Validity::default() => CheckResult::skipped("The credential has no validity attributes"),
+            Ok(_) => CheckResult::pass(format!("The credential is valid at {}", time.value)),
+            Err(err) => CheckResult::fail(err.to_string()),
+        },
+    };
+
+    let revocation = match (&options.revocation, &hash) {
+        (None, _) => CheckResult::skipped("No revocation list or tree was given"),
+        (Some(_), None) => CheckResult::skipped("The credential could not be hashed"),
+        (Some(revocation), Some(hash)) => revocation.check(hash),
+    };
+
+    let holder_binding = match &options.holder {
+        None => CheckResult::skipped("No holder was given"),
+        Some(holder) => match message_subject(&attributes) {
+            Some(subject) if subject == *holder => CheckResult::pass(format!("Holder {} is the subject", holder)),
+            Some(subject) => CheckResult::fail(format!("Holder {} is not the subject {}", holder, subject)),
+            None => CheckResult::fail("The credential has no subject address"),
+        },
+    };
+
+    let mut report = VerificationReport { valid: false, hash, signature, issuer_trusted, schema, expiry, revocation, holder_binding };
+    report.valid = report.signature.status == CheckStatus::Pass
+        && report.checks().iter().all(|(_, check)| check.status != CheckStatus::Fail);
+    report
+}
+
+fn check_schema<N: NetworkNative>(schema: &IndexMap<String, LiteralType>, attributes: &IndexMap<String, Plaintext<N>>) -> CheckResult {
+    for (name, expected) in schema {
+        match attributes.get(name) {
+            None => return CheckResult::fail(format!("Attribute '{}' is missing", name)),
+            Some(Plaintext::Literal(literal, _)) if literal.to_type() == *expected => {}
+            Some(value) => return CheckResult::fail(format!("Attribute '{}' is {}, expected a {}", name, value, expected)),
+        }
+    }
+    if let Some(name) = attributes.keys().find(|name| !schema.contains_key(*name)) {
+        return CheckResult::fail(format!("Attribute '{}' is not in the schema", name));
+    }
+    CheckResult::pass("Attributes match the schema")
+}
+
+fn message_subject<N: NetworkNative>(attributes: &Result<IndexMap<String, Plaintext<N>>, anyhow::Error>) -> Option<Address<N>> {
+    match attributes.as_ref().ok()?.get(SUBJECT_ATTRIBUTE)? {
+        Plaintext::Literal(Literal::Address(address), _) => Some(*address),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, ISSUER, SUBJECT, credentials};
+    use serde_json::json;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    fn signed() -> (SignInboundMessage, Signature<N>, String) {
+        let message = SignInboundMessage { data: credentials() };
+        let signer = PrivateKeySigner::<N>::from_str(PRIVATE_KEY).unwrap();
+        let checks = SignChecks::default().at(ReferenceTime::date(20250101).unwrap());
+        let (signature, hash) = sign_message_with_checks(&signer, message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger).unwrap();
+        (message, Signature::from_str(&signature).unwrap(), hash)
+    }
+
+    fn verify(message: SignInboundMessage, signature: &Signature<N>, options: JsonValue) -> VerificationReport {
+        let options = VerificationOptions::<N>::from_json(options).unwrap();
+        let issuer = Address::<N>::from_str(ISSUER).unwrap();
+        verify_credential_report(message, signature, &issuer, HashAlgorithm::POSEIDON2, HashOutputType::Field, &options, &StdoutLogger)
+    }
+
+    #[test]
+    fn test_report_all_checks_pass() {
+        let (message, signature, hash) = signed();
+        let report = verify(message, &signature, json!({
+            "trustedIssuers": [ISSUER],
+            "schema": { "issuer": "address", "subject": "address", "dob": "u32", "nationality": "field", "expiry": "u32" },
+            "currentTime": 20250101,
+            "revoked": ["1field"],
+            "holder": SUBJECT,
+        }));
+
+        assert!(report.valid);
+        assert_eq!(report.hash, Some(hash));
+        assert!(report.checks().iter().all(|(_, check)| check.status == CheckStatus::Pass));
+
+        let json: JsonValue = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["expiry"]["status"], "pass");
+    }
+
+    #[test]
+    fn test_report_validity_window() {
+        let (message, signature, _) = signed();
+        let report = verify(message.clone(), &signature, json!({ "currentTime": 20300101 }));
+        assert_eq!(report.expiry.status, CheckStatus::Pass);
+
+        let report = verify(message.clone(), &signature, json!({ "currentTime": 20300101, "timeBasis": "block_height" }));
+        assert_eq!(report.expiry.status, CheckStatus::Pass);
+        let report = verify(message, &signature, json!({ "currentTime": 20300102, "timeBasis": "block_height" }));
+        assert_eq!(report.expiry.status, CheckStatus::Fail);
+        assert!(!report.valid);
+
+        assert!(VerificationOptions::<N>::from_json(json!({ "currentTime": 20301301 })).is_err());
+        assert!(VerificationOptions::<N>::from_json(json!({ "timeBasis": "date" })).is_err());
+    }
+
+    #[test]
+    fn test_report_skips_and_failures() {
+        let (message, signature, hash) = signed();
+        let report = verify(message.clone(), &signature, json!({}));
+        assert!(report.valid);
+        assert_eq!(report.signature.status, CheckStatus::Pass);
+        assert!(report.checks()[1..].iter().all(|(_, check)| check.status == CheckStatus::Skipped));
+
+        let report = verify(message, &signature, json!({
+            "trustedIssuers": [SUBJECT],
+            "schema": { "dob": "field" },
+            "currentTime": 20300102,
+            "revoked": [hash],
+            "holder": ISSUER,
+        }));
+        assert!(!report.valid);
+        assert_eq!(report.signature.status, CheckStatus::Pass);
+        assert!(report.checks()[1..].iter().all(|(_, check)| check.status == CheckStatus::Fail));
+
+        let mut tampered = SignInboundMessage { data: credentials() };
+        tampered.data["dob"] = json!("20010101u32");
+        let report = verify(tampered, &signature, json!({}));
+        assert!(!report.valid);
+        assert_eq!(report.signature.status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_revocation_list() {
+        let (message, signature, hash) = signed();
+        let report = verify(message, &signature, json!({ "revoked": ["1field", format!(" {} ", hash)] }));
+        assert_eq!(report.revocation.status, CheckStatus::Fail);
+
+        assert!(VerificationOptions::<N>::from_json(json!({ "unknown": true })).is_err());
+    }
+
+    #[test]
+    fn test_revocation_tree() {
+        let (message, signature, hash) = signed();
+        let tree = MerkleTree::<N>::new(["1field", hash.as_str(), "3field", "4field"].iter().map(|leaf| Field::from_str(leaf).unwrap()).collect()).unwrap();
+        let tree_options = |proof: Vec<Field<N>>| json!({
+            "revocationTree": { "root": tree.root().to_string(), "proof": proof.iter().map(|sibling| sibling.to_string()).collect::<Vec<_>>() },
+        });
+
+        let report = verify(message.clone(), &signature, tree_options(tree.get_proof(1).unwrap()));
+        assert_eq!(report.revocation.status, CheckStatus::Fail);
+        assert!(!report.valid);
+
+        // The proof of another leaf does not place this credential in the tree.
+        let report = verify(message, &signature, tree_options(tree.get_proof(2).unwrap()));
+        assert_eq!(report.revocation.status, CheckStatus::Pass);
+
+        assert!(VerificationOptions::<N>::from_json(json!({ "revocationTree": ["1field", "2field"] })).is_err());
+        assert!(VerificationOptions::<N>::from_json(json!({ "revocationTree": { "root": "1field", "proof": ["x"] } })).is_err());
+        assert!(VerificationOptions::<N>::from_json(json!({ "revoked": [], "revocationTree": { "root": "1field", "proof": [] } })).is_err());
+    }
+
+    #[test]
+    fn test_report_checks_expiry_only_at_current_time() {
+        let mut message = SignInboundMessage { data: credentials() };
+        message.data["expiry"] = json!("20200101u32");
+        let signer = PrivateKeySigner::<N>::from_str(PRIVATE_KEY).unwrap();
+        let checks = SignChecks::default().at(ReferenceTime::date(20190101).unwrap());
+        let (signature, _) = sign_message_with_checks(&signer, message.clone(), HashAlgorithm::POSEIDON2, HashOutputType::Field, &checks, &StdoutLogger).unwrap();
+        let signature = Signature::from_str(&signature).unwrap();
+
+        // An expired credential still has a valid signature; only the expiry check judges the window.
+        let report = verify(message.clone(), &signature, json!({}));
+        assert_eq!(report.signature.status, CheckStatus::Pass);
+        assert_eq!(report.expiry.status, CheckStatus::Skipped);
+        assert!(report.valid);
+
+        let report = verify(message.clone(), &signature, json!({ "currentTime": 20191231 }));
+        assert_eq!(report.signature.status, CheckStatus::Pass);
+        assert_eq!(report.expiry.status, CheckStatus::Pass);
+
+        let report = verify(message, &signature, json!({ "currentTime": 20200102 }));
+        assert_eq!(report.signature.status, CheckStatus::Pass);
+        assert_eq!(report.expiry.status, CheckStatus::Fail);
+        assert!(!report.valid);
+    }
+}
@@ -0,0 +1,126 @@
+use super::*;
+use std::fmt;
+use zeroize::{Zeroize, Zeroizing};
+
+/// A private key string that is wiped from memory when dropped.
+pub struct SecretString(Zeroizing<String>);
+
+impl SecretString {
+    /// Takes ownership of the string, so no unwiped copy is left behind.
+    pub fn new(secret: String) -> Self {
+        Self(Zeroizing::new(secret))
+    }
+
+    /// Copies the UTF-8 secret out of `bytes` and clears `bytes`, whether or not it is valid.
+    pub fn from_bytes(bytes: &mut [u8]) -> Result<Self, CustomError> {
+        let secret = std::str::from_utf8(bytes)
+            .map(|secret| Self::new(secret.to_string()))
+            .map_err(|_| anyhow!("Private key bytes are not valid UTF-8").into());
+        bytes.zeroize();
+        secret
+    }
+
+    /// Returns the secret.
+    pub fn expose(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString(<redacted>)")
+    }
+}
+
+/// A parsed private key that is overwritten when dropped.
+///
+/// The wipe is best effort. `PrivateKey<N>` is `Copy` and its layout is private to snarkVM, so the
+/// key is overwritten with the fixed key derived from a zero seed through its public constructor,
+/// and copies made elsewhere (by snarkVM while signing, or moves of the wrapper) are not wiped.
+/// Expose the key by reference rather than copying it out.
+pub struct SecretPrivateKey<N: NetworkNative>(PrivateKey<N>);
+
+impl<N: NetworkNative> SecretPrivateKey<N> {
+    /// Wraps the private key.
+    pub fn new(private_key: PrivateKey<N>) -> Self {
+        Self(private_key)
+    }
+
+    /// Parses the private key from a secret string.
+    pub fn from_secret(secret: &SecretString) -> Result<Self, CustomError> {
+        let private_key = PrivateKey::<N>::from_str(secret.expose())
+            .map_err(|e| anyhow!("Failed to parse private key: {}", e))?;
+        Ok(Self::new(private_key))
+    }
+
+    /// Returns the private key.
+    pub fn expose(&self) -> &PrivateKey<N> {
+        &self.0
+    }
+}
+
+impl<N: NetworkNative> SecretPrivateKey<N> {
+    // Overwrites the key with the key of the zero seed.
+    fn wipe(&mut self) {
+        if let Ok(blank) = PrivateKey::<N>::try_from(Field::zero()) {
+            self.0 = blank;
+            // Keeps the store from being dropped as dead right before the key goes out of scope.
+            std::hint::black_box(&mut self.0);
+        }
+    }
+}
+
+impl<N: NetworkNative> Drop for SecretPrivateKey<N> {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+impl<N: NetworkNative> fmt::Debug for SecretPrivateKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretPrivateKey(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::PRIVATE_KEY;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    #[test]
+    fn test_secret_string_from_bytes_clears_buffer() {
+        let mut bytes = PRIVATE_KEY.as_bytes().to_vec();
+        let secret = SecretString::from_bytes(&mut bytes).unwrap();
+        assert_eq!(secret.expose(), PRIVATE_KEY);
+        assert!(bytes.iter().all(|byte| *byte == 0));
+
+        let mut invalid = vec![0xff, 0xfe, 0x41];
+        assert!(SecretString::from_bytes(&mut invalid).is_err());
+        assert!(invalid.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn test_secret_private_key() {
+        let secret = SecretString::new(PRIVATE_KEY.to_string());
+        let private_key = SecretPrivateKey::<N>::from_secret(&secret).unwrap();
+        assert_eq!(private_key.expose().to_string(), PRIVATE_KEY);
+        assert!(!format!("{:?} {:?}", secret, private_key).contains("APrivateKey1"));
+    }
+
+    #[test]
+    fn test_secret_private_key_wipe() {
+        let mut private_key = SecretPrivateKey::<N>::new(PrivateKey::from_str(PRIVATE_KEY).unwrap());
+        private_key.wipe();
+        assert!(private_key.0.seed().is_zero());
+        assert_eq!(private_key.0, PrivateKey::try_from(Field::zero()).unwrap());
+    }
+}
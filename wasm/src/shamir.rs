@@ -0,0 +1,252 @@
+use super::*;
+use rand::{rngs::OsRng, RngCore};
+use ring::digest::{digest, SHA256};
+use snarkvm_utilities::{FromBytes, ToBytes};
+use zeroize::Zeroizing;
+
+/// The current key share format version.
+pub const KEY_SHARE_VERSION: u32 = 1;
+
+const CHECKSUM_SIZE: usize = 8;
+
+/// One share of an issuer private key seed, split with Shamir's secret sharing over GF(256).
+///
+/// Every share names the issuer address it belongs to and carries a checksum over its own
+/// contents, so corrupted or mixed-up shares are rejected before recovery and the recovered
+/// key is checked against the address.
+///
+/// The GF(256) arithmetic used to split and recover shares is not constant time: its branches
+/// and loop counts depend on the secret bytes, so it should not run where timing is observable.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyShare {
+    pub version: u32,
+    pub network: u16,
+    pub address: String,
+    pub threshold: u8,
+    pub shares: u8,
+    pub index: u8,
+    pub data: String,
+    /// The first 8 bytes of an unkeyed SHA-256 over the other fields. It only catches accidental
+    /// corruption or mixed-up shares: anyone can recompute it, so it does not authenticate a share.
+    pub checksum: String,
+}
+
+impl KeyShare {
+    fn compute_checksum(&self) -> String {
+        let preimage = format!(
+            "zpass-key-share:{}:{}:{}:{}:{}:{}:{}",
+            self.version, self.network, self.address, self.threshold, self.shares, self.index, self.data
+        );
+        hex::encode(&digest(&SHA256, preimage.as_bytes()).as_ref()[..CHECKSUM_SIZE])
+    }
+
+    /// Checks the share version and checksum.
+    pub fn verify(&self) -> Result<(), CustomError> {
+        if self.version != KEY_SHARE_VERSION {
+            return Err(anyhow!("Unsupported key share version {}", self.version).into());
+        }
+        if self.checksum != self.compute_checksum() {
+            return Err(anyhow!("Key share {} failed its integrity check", self.index).into());
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeyShare {
+    type Err = CustomError;
+
+    /// Parses and verifies a share from its JSON encoding.
+    fn from_str(share: &str) -> Result<Self, Self::Err> {
+        let share: KeyShare = serde_json::from_str(share)
+            .map_err(|e| anyhow!("Malformed key share: {}", e))?;
+        share.verify()?;
+        Ok(share)
+    }
+}
+
+impl std::fmt::Display for KeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", serde_json::to_string(self).map_err(|_| std::fmt::Error)?)
+    }
+}
+
+/// Splits the private key seed into `shares` shares, any `threshold` of which recover the key.
+pub fn split_private_key<N: NetworkNative>(private_key: &PrivateKey<N>, threshold: u8, shares: u8) -> Result<Vec<KeyShare>, CustomError> {
+    if threshold < 2 || threshold > shares {
+        return Err(anyhow!("Threshold must be between 2 and the number of shares ({}), got {}", shares, threshold).into());
+    }
+    let address = Address::<N>::try_from(private_key)
+        .map_err(|e| anyhow!("Failed to parse issuer address: {}", e))?;
+    let seed = Zeroizing::new(private_key.to_bytes_le()
+        .map_err(|e| anyhow!("Failed to serialize private key: {}", e))?);
+
+    // One random polynomial per seed byte, with the byte as its constant term.
+    let mut coefficients = Zeroizing::new(vec![0u8; seed.len() * (threshold as usize - 1)]);
+    OsRng.fill_bytes(&mut coefficients);
+
+    let key_shares = (1..=shares)
+        .map(|x| {
+            let mut data = Zeroizing::new(Vec::with_capacity(seed.len()));
+            for (i, byte) in seed.iter().enumerate() {
+                let polynomial = &coefficients[i * (threshold as usize - 1)..(i + 1) * (threshold as usize - 1)];
+                // Horner's rule, highest degree coefficient first.
+                let y = polynomial.iter().rev().fold(0u8, |acc, c| gf_mul(acc, x) ^ c);
+                data.push(gf_mul(y, x) ^ byte);
+            }
+            let mut share = KeyShare {
+                version: KEY_SHARE_VERSION,
+                network: N::ID,
+                address: address.to_string(),
+                threshold,
+                shares,
+                index: x,
+                data: hex::encode(data.as_slice()),
+                checksum: String::new(),
+            };
+            share.checksum = share.compute_checksum();
+            share
+        })
+        .collect();
+    Ok(key_shares)
+}
+
+/// Recovers the private key from at least `threshold` shares of the same split.
+pub fn recover_private_key<N: NetworkNative>(shares: &[KeyShare]) -> Result<SecretPrivateKey<N>, CustomError> {
+    let first = shares.first().ok_or_else(|| anyhow!("No key shares were provided"))?;
+    for share in shares {
+        share.verify()?;
+        if share.network != N::ID {
+            return Err(anyhow!("Key share {} belongs to network {}, expected {}", share.index, share.network, N::ID).into());
+        }
+        if (&share.address, share.threshold, share.shares) != (&first.address, first.threshold, first.shares) {
+            return Err(anyhow!("Key share {} belongs to a different split", share.index).into());
+        }
+    }
+
+    let mut indices: Vec<u8> = shares.iter().map(|share| share.index).collect();
+    indices.sort_unstable();
+    indices.dedup();
+    if indices.len() != shares.len() || indices.contains(&0) {
+        return Err(anyhow!("Key shares must have distinct non-zero indices").into());
+    }
+    if shares.len() < first.threshold as usize {
+        return Err(anyhow!("{} key shares are required, got {}", first.threshold, shares.len()).into());
+    }
+
+    let shares = &shares[..first.threshold as usize];
+    let data = shares
+        .iter()
+        .map(|share| hex::decode(&share.data).map(Zeroizing::new))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow!("Invalid key share data: {}", e))?;
+    let length = data[0].len();
+    if data.iter().any(|bytes| bytes.len() != length) {
+        return Err(anyhow!("Key shares have mismatched lengths").into());
+    }
+
+    // Lagrange interpolation at x = 0, byte by byte.
+    let mut seed = Zeroizing::new(vec![0u8; length]);
+    for (j, share) in shares.iter().enumerate() {
+        let basis = shares
+            .iter()
+            .filter(|other| other.index != share.index)
+            .fold(1u8, |acc, other| gf_mul(acc, gf_div(other.index, other.index ^ share.index)));
+        for (byte, y) in seed.iter_mut().zip(data[j].iter()) {
+            *byte ^= gf_mul(basis, *y);
+        }
+    }
+
+    let private_key = SecretPrivateKey::new(PrivateKey::<N>::from_bytes_le(&seed)
+        .map_err(|e| anyhow!("Failed to parse recovered private key: {}", e))?);
+    if Address::<N>::try_from(private_key.expose())?.to_string() != first.address {
+        return Err(anyhow!("Recovered private key does not match the issuer address {}", first.address).into());
+    }
+    Ok(private_key)
+}
+
+// Multiplication in GF(2^8) modulo the AES polynomial x^8 + x^4 + x^3 + x + 1.
+// Not constant time: the branches and the loop count depend on the operands.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+// Division in GF(2^8), using a^254 = a^-1.
+fn gf_div(a: u8, b: u8) -> u8 {
+    let mut inverse = 1u8;
+    for _ in 0..254 {
+        inverse = gf_mul(inverse, b);
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, SUBJECT};
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    fn split(threshold: u8, shares: u8) -> Vec<KeyShare> {
+        split_private_key(&PrivateKey::<N>::from_str(PRIVATE_KEY).unwrap(), threshold, shares).unwrap()
+    }
+
+    #[test]
+    fn test_recover_from_any_threshold_subset() {
+        let shares = split(3, 5);
+        assert_eq!(shares.len(), 5);
+
+        for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+            let subset: Vec<KeyShare> = subset.iter().map(|i| shares[*i].clone()).collect();
+            let private_key = recover_private_key::<N>(&subset).unwrap();
+            assert_eq!(private_key.expose().to_string(), PRIVATE_KEY);
+        }
+        assert!(recover_private_key::<N>(&shares).is_ok());
+    }
+
+    #[test]
+    fn test_recover_rejects_insufficient_or_duplicate_shares() {
+        let shares = split(3, 5);
+        assert!(recover_private_key::<N>(&shares[..2]).is_err());
+        assert!(recover_private_key::<N>(&[shares[0].clone(), shares[0].clone(), shares[1].clone()]).is_err());
+        assert!(recover_private_key::<MainnetV0>(&shares[..3]).is_err());
+        assert!(recover_private_key::<N>(&[]).is_err());
+    }
+
+    #[test]
+    fn test_share_integrity() {
+        let shares = split(2, 3);
+        let encoded = shares[0].to_string();
+        assert_eq!(KeyShare::from_str(&encoded).unwrap(), shares[0]);
+
+        let mut corrupted = shares[0].clone();
+        corrupted.data.replace_range(0..2, if corrupted.data.starts_with("00") { "01" } else { "00" });
+        assert!(KeyShare::from_str(&corrupted.to_string()).is_err());
+        assert!(recover_private_key::<N>(&[corrupted, shares[1].clone()]).is_err());
+
+        let other = split(2, 3);
+        let mut mixed = other[1].clone();
+        mixed.address = SUBJECT.to_string();
+        mixed.checksum = mixed.compute_checksum();
+        assert!(recover_private_key::<N>(&[shares[0].clone(), mixed]).is_err());
+    }
+
+    #[test]
+    fn test_split_rejects_invalid_threshold() {
+        let private_key = PrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        assert!(split_private_key(&private_key, 1, 3).is_err());
+        assert!(split_private_key(&private_key, 4, 3).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use super::*;
+use rand::rngs::OsRng;
+use std::cell::{Cell, OnceCell};
+
+/// An issuer key able to sign credential messages.
+///
+/// Signing APIs take a signer rather than a private key string, so the key itself can live
+/// outside of the wasm process (see `RemoteSigner`).
+pub trait CredentialSigner<N: NetworkNative> {
+    /// Returns the issuer address the signatures verify against.
+    fn address(&self) -> Result<Address<N>, CustomError>;
+
+    /// Signs the given field message.
+    fn sign(&self, message: &[Field<N>]) -> Result<Signature<N>, CustomError>;
+}
+
+/// A signer holding the issuer private key in memory.
+pub struct PrivateKeySigner<N: NetworkNative> {
+    private_key: SecretPrivateKey<N>,
+    address: Address<N>,
+}
+
+impl<N: NetworkNative> PrivateKeySigner<N> {
+    /// Creates a signer from a parsed private key, which is wiped when the signer is dropped.
+    pub fn new(private_key: SecretPrivateKey<N>) -> Result<Self, CustomError> {
+        let address = Address::<N>::try_from(private_key.expose())
+            .map_err(|e| anyhow!("Failed to parse issuer address: {}", e))?;
+        Ok(Self { private_key, address })
+    }
+
+    /// Creates a signer from a secret `APrivateKey1...` string.
+    pub fn from_secret(private_key: &SecretString) -> Result<Self, CustomError> {
+        Self::new(SecretPrivateKey::from_secret(private_key)?)
+    }
+}
+
+impl<N: NetworkNative> FromStr for PrivateKeySigner<N> {
+    type Err = CustomError;
+
+    /// Creates a signer from an `APrivateKey1...` string.
+    fn from_str(private_key: &str) -> Result<Self, Self::Err> {
+        Self::from_secret(&SecretString::new(private_key.to_string()))
+    }
+}
+
+impl<N: NetworkNative> CredentialSigner<N> for PrivateKeySigner<N> {
+    fn address(&self) -> Result<Address<N>, CustomError> {
+        Ok(self.address)
+    }
+
+    fn sign(&self, message: &[Field<N>]) -> Result<Signature<N>, CustomError> {
+        let (signature, _nonce) = sign_message_with_private_key(self.private_key.expose(), message, &mut OsRng)?;
+        Ok(signature)
+    }
+}
+
+/// Carries a single JSON-RPC request to a signer and returns its raw response.
+pub trait RpcTransport {
+    fn call(&self, request: &str) -> Result<String, CustomError>;
+}
+
+/// Sends newline-delimited JSON-RPC requests over a local TCP connection.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct TcpTransport {
+    address: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TcpTransport {
+    /// Creates a transport for the signer listening on `address`, e.g. `127.0.0.1:7878`.
+    pub fn new(address: impl Into<String>) -> Self {
+        Self { address: address.into() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RpcTransport for TcpTransport {
+    fn call(&self, request: &str) -> Result<String, CustomError> {
+        use std::io::{BufRead, BufReader, Write};
+
+        let mut stream = std::net::TcpStream::connect(&self.address)
+            .map_err(|e| anyhow!("Failed to connect to signer at {}: {}", self.address, e))?;
+        stream.write_all(format!("{}\n", request).as_bytes())
+            .map_err(|e| anyhow!("Failed to send request to signer: {}", e))?;
+
+        let mut response = String::new();
+        BufReader::new(stream).read_line(&mut response)
+            .map_err(|e| anyhow!("Failed to read response from signer: {}", e))?;
+        Ok(response)
+    }
+}
+
+/// Hands JSON-RPC requests to a synchronous JavaScript function `(request: string) => string`.
+pub struct JsFunctionTransport {
+    function: js_sys::Function,
+}
+
+impl JsFunctionTransport {
+    pub fn new(function: js_sys::Function) -> Self {
+        Self { function }
+    }
+}
+
+impl RpcTransport for JsFunctionTransport {
+    fn call(&self, request: &str) -> Result<String, CustomError> {
+        let response = self.function
+            .call1(&JsValue::NULL, &JsValue::from_str(request))
+            .map_err(|e| anyhow!("Signer callback failed: {:?}", e))?;
+        response
+            .as_string()
+            .ok_or_else(|| anyhow!("Signer callback must return a string").into())
+    }
+}
+
+/// The methods of the signer JSON-RPC protocol.
+pub const RPC_METHOD_ADDRESS: &str = "zpass_address";
+pub const RPC_METHOD_SIGN: &str = "zpass_sign";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    id: u64,
+    method: String,
+    #[serde(default)]
+    params: JsonValue,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RpcResponse {
+    jsonrpc: String,
+    /// The request id, or null when the request could not be parsed.
+    id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<JsonValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+/// A signer delegating to an external process over a JSON-RPC 2.0 protocol.
+///
+/// Requests are `{"jsonrpc": "2.0", "id", "method", "params"}` objects, where `params` always
+/// carries the numeric network `id`:
+///
+/// - `zpass_address` returns the issuer address string.
+/// - `zpass_sign` takes `message`, an array of field strings, and returns the signature string.
+///
+/// Every returned signature is checked against the signer address before it is accepted. The
+/// address is requested once and cached for the lifetime of the signer.
+pub struct RemoteSigner<N: NetworkNative, T: RpcTransport> {
+    transport: T,
+    next_id: Cell<u64>,
+    address: OnceCell<Address<N>>,
+}
+
+impl<N: NetworkNative, T: RpcTransport> RemoteSigner<N, T> {
+    /// Creates a signer speaking to the remote signer through `transport`.
+    pub fn new(transport: T) -> Self {
+        Self { transport, next_id: Cell::new(1), address: OnceCell::new() }
+    }
+
+    fn request(&self, method: &str, mut params: JsonValue) -> Result<JsonValue, CustomError> {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        params["network"] = JsonValue::from(N::ID);
+
+        let request = RpcRequest { jsonrpc: "2.0".to_string(), id, method: method.to_string(), params };
+        let request = serde_json::to_string(&request)
+            .map_err(|e| anyhow!("Failed to serialize signer request: {}", e))?;
+        let response: RpcResponse = serde_json::from_str(self.transport.call(&request)?.trim())
+            .map_err(|e| anyhow!("Malformed signer response: {}", e))?;
+
+        // Errors are surfaced first: a signer that could not parse the request answers with a null id.
+        if let Some(error) = response.error {
+            return Err(anyhow!("Signer returned error {}: {}", error.code, error.message).into());
+        }
+        if response.id != Some(id) {
+            let found = response.id.map_or("null".to_string(), |id| id.to_string());
+            return Err(anyhow!("Signer response id {} does not match request id {}", found, id).into());
+        }
+        response.result.ok_or_else(|| anyhow!("Signer response has no result").into())
+    }
+
+    fn request_string(&self, method: &str, params: JsonValue) -> Result<String, CustomError> {
+        self.request(method, params)?
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Signer result for '{}' must be a string", method).into())
+    }
+}
+
+impl<N: NetworkNative, T: RpcTransport> CredentialSigner<N> for RemoteSigner<N, T> {
+    fn address(&self) -> Result<Address<N>, CustomError> {
+        if let Some(address) = self.address.get() {
+            return Ok(*address);
+        }
+        let address = self.request_string(RPC_METHOD_ADDRESS, serde_json::json!({}))?;
+        let address = Address::<N>::from_str(&address).map_err(|e| anyhow!("Signer returned an invalid address: {}", e))?;
+        Ok(*self.address.get_or_init(|| address))
+    }
+
+    fn sign(&self, message: &[Field<N>]) -> Result<Signature<N>, CustomError> {
+        let fields: Vec<String> = message.iter().map(|field| field.to_string()).collect();
+        let signature = self.request_string(RPC_METHOD_SIGN, serde_json::json!({ "message": fields }))?;
+        let signature = Signature::<N>::from_str(&signature)
+            .map_err(|e| anyhow!("Signer returned an invalid signature: {}", e))?;
+
+        if !verify_signature_with_address_and_message(&signature, &self.address()?, message) {
+            return Err(anyhow!("Signer returned a signature that does not verify against its address").into());
+        }
+        Ok(signature)
+    }
+}
+
+/// Answers one signer JSON-RPC request with `signer`, for building a local signer process.
+pub fn handle_rpc_request<N: NetworkNative>(signer: &dyn CredentialSigner<N>, request: &str) -> String {
+    let (id, result) = match serde_json::from_str::<RpcRequest>(request.trim()) {
+        Ok(request) => (Some(request.id), answer_rpc_request(signer, &request)),
+        Err(e) => (None, Err(RpcError { code: -32700, message: format!("Parse error: {}", e) })),
+    };
+    let (result, error) = match result {
+        Ok(result) => (Some(result), None),
+        Err(error) => (None, Some(error)),
+    };
+    let response = RpcResponse { jsonrpc: "2.0".to_string(), id, result, error };
+    serde_json::to_string(&response).unwrap_or_default()
+}
+
+fn answer_rpc_request<N: NetworkNative>(signer: &dyn CredentialSigner<N>, request: &RpcRequest) -> Result<JsonValue, RpcError> {
+    let invalid_params = |message: String| RpcError { code: -32602, message };
+    let internal = |e: CustomError| RpcError { code: -32000, message: e.to_string() };
+
+    if request.params.get("network").and_then(JsonValue::as_u64) != Some(N::ID as u64) {
+        return Err(invalid_params(format!("Signer only serves network {}", N::ID)));
+    }
+
+    match request.method.as_str() {
+        RPC_METHOD_ADDRESS => Ok(JsonValue::from(signer.address().map_err(internal)?.to_string())),
+        RPC_METHOD_SIGN => {
+            let message = request.params.get("message")
+                .and_then(JsonValue::as_array)
+                .ok_or_else(|| invalid_params("Missing 'message' array".to_string()))?
+                .iter()
+                .map(|field| field.as_str()
+                    .and_then(|field| Field::<N>::from_str(field).ok())
+                    .ok_or_else(|| invalid_params(format!("Invalid field {}", field))))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(JsonValue::from(signer.sign(&message).map_err(internal)?.to_string()))
+        }
+        method => Err(RpcError { code: -32601, message: format!("Unknown method '{}'", method) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, ISSUER};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    // Spawns a stand-in signer answering `requests` connections, returning its address.
+    fn spawn_local_signer(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        std::thread::spawn(move || {
+            let signer = PrivateKeySigner::<N>::from_str(PRIVATE_KEY).unwrap();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                BufReader::new(&stream).read_line(&mut request).unwrap();
+                let response = handle_rpc_request(&signer, &request);
+                stream.write_all(format!("{}\n", response).as_bytes()).unwrap();
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn test_private_key_signer() {
+        let signer = PrivateKeySigner::<N>::from_str(PRIVATE_KEY).unwrap();
+        assert_eq!(signer.address().unwrap().to_string(), ISSUER);
+
+        let message = string_to_value_fields::<N>("123field");
+        let signature = signer.sign(&message).unwrap();
+        assert!(verify_signature_with_address_and_message(&signature, &signer.address().unwrap(), &message));
+
+        assert!(PrivateKeySigner::<N>::from_str("APrivateKey1invalid").is_err());
+    }
+
+    #[test]
+    fn test_remote_signer_over_tcp() {
+        let address = spawn_local_signer(3);
+        let signer = RemoteSigner::<N, _>::new(TcpTransport::new(address));
+        assert_eq!(signer.address().unwrap().to_string(), ISSUER);
+
+        // The address is cached, so both signatures take a single request each.
+        let message = string_to_value_fields::<N>("123field");
+        for _ in 0..2 {
+            let signature = signer.sign(&message).unwrap();
+            assert!(verify_signature_with_address_and_message(&signature, &Address::<N>::from_str(ISSUER).unwrap(), &message));
+        }
+    }
+
+    #[test]
+    fn test_handle_rpc_request_errors() {
+        let signer = PrivateKeySigner::<N>::from_str(PRIVATE_KEY).unwrap();
+
+        let response: JsonValue = serde_json::from_str(&handle_rpc_request(&signer, "not json")).unwrap();
+        assert_eq!(response["id"], JsonValue::Null);
+        assert_eq!(response["error"]["code"], -32700);
+
+        let response = handle_rpc_request(&signer, r#"{"jsonrpc":"2.0","id":7,"method":"zpass_sign","params":{"network":0}}"#);
+        assert!(response.contains("-32602"));
+
+        let response = handle_rpc_request(&signer, r#"{"jsonrpc":"2.0","id":8,"method":"zpass_unknown","params":{"network":1}}"#);
+        assert!(response.contains("-32601"));
+    }
+
+    // Hands the signer a truncated request, as a broken connection would.
+    struct TruncatingTransport(PrivateKeySigner<N>);
+
+    impl RpcTransport for TruncatingTransport {
+        fn call(&self, request: &str) -> Result<String, CustomError> {
+            Ok(handle_rpc_request(&self.0, &request[..request.len() / 2]))
+        }
+    }
+
+    #[test]
+    fn test_remote_signer_surfaces_parse_errors() {
+        let signer = RemoteSigner::<N, _>::new(TruncatingTransport(PrivateKeySigner::from_str(PRIVATE_KEY).unwrap()));
+        let error = signer.address().unwrap_err().to_string();
+        assert!(error.contains("-32700"), "{}", error);
+    }
+}
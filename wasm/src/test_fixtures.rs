@@ -0,0 +1,14 @@
+use super::*;
+use serde_json::json;
+
+/// The private key of the test issuer.
+pub const PRIVATE_KEY: &str = "APrivateKey1zkp5LqRmm7535XfiX77VPQEgsS2Dj1B2DvH4QNP1UYrHEoR";
+/// The address of `PRIVATE_KEY`.
+pub const ISSUER: &str = "aleo1ekyuzclmcw3aj7qncsxxaapxem82mgrd8zadgrrvl5k705zx6q9s7usuqy";
+/// The subject of the test credentials.
+pub const SUBJECT: &str = "aleo14w44zfrehup9g894j7tgeyz5gsjuxn0nfn09vd2fvpznrg85rs8skywkte";
+
+/// A zPass credential issued by `ISSUER` to `SUBJECT`.
+pub fn credentials() -> JsonValue {
+    json!({ "issuer": ISSUER, "subject": SUBJECT, "dob": "20000101u32", "nationality": "840field", "expiry": "20300101u32" })
+}
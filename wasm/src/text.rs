@@ -0,0 +1,180 @@
+use super::*;
+use snarkvm_console::prelude::FromBits;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// The number of text bytes packed into each field.
+pub const TEXT_BYTES_PER_FIELD: usize = 31;
+
+// The text length is stored as a little-endian u16 before the text.
+const LENGTH_PREFIX_SIZE: usize = 2;
+
+/// A text attribute layout: NFC-normalized UTF-8, prefixed with its byte length and packed into a
+/// fixed number of fields.
+///
+/// The same text always encodes to the same fields whatever its input normalization, and the
+/// field count is fixed so the attribute has a fixed type in the Leo struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "TextAttributeFields")]
+pub struct TextAttribute {
+    fields: usize,
+    max_chars: usize,
+}
+
+// The serialized layout, validated by `TextAttribute::new` when deserializing.
+#[derive(Deserialize)]
+struct TextAttributeFields {
+    fields: usize,
+    max_chars: usize,
+}
+
+impl TryFrom<TextAttributeFields> for TextAttribute {
+    type Error = CustomError;
+
+    fn try_from(layout: TextAttributeFields) -> Result<Self, Self::Error> {
+        Self::new(layout.fields, layout.max_chars)
+    }
+}
+
+impl TextAttribute {
+    /// Creates a layout over `fields` fields holding at most `max_chars` characters.
+    pub fn new(fields: usize, max_chars: usize) -> Result<Self, CustomError> {
+        let fits = fields.checked_mul(TEXT_BYTES_PER_FIELD).is_some_and(|bytes| bytes <= u16::MAX as usize + LENGTH_PREFIX_SIZE);
+        if fields == 0 || !fits {
+            return Err(anyhow!("A text attribute must span between 1 and {} fields, got {}", (u16::MAX as usize + LENGTH_PREFIX_SIZE) / TEXT_BYTES_PER_FIELD, fields).into());
+        }
+        Ok(Self { fields, max_chars })
+    }
+
+    /// Returns the number of fields.
+    pub fn fields(&self) -> usize {
+        self.fields
+    }
+
+    /// Returns the maximum number of characters.
+    pub fn max_chars(&self) -> usize {
+        self.max_chars
+    }
+
+    /// Returns the maximum number of UTF-8 bytes. `new` bounds it by `u16::MAX`, so it cannot overflow.
+    pub fn capacity(&self) -> usize {
+        self.fields * TEXT_BYTES_PER_FIELD - LENGTH_PREFIX_SIZE
+    }
+
+    /// Normalizes and encodes the text.
+    pub fn encode<N: NetworkNative>(&self, text: &str) -> Result<Vec<Field<N>>, CustomError> {
+        let text = normalize_text(text);
+        let chars = text.chars().count();
+        if chars > self.max_chars {
+            return Err(anyhow!("Text is {} characters long, the maximum is {}", chars, self.max_chars).into());
+        }
+        if text.len() > self.capacity() {
+            return Err(anyhow!("Text is {} bytes long, the maximum is {}", text.len(), self.capacity()).into());
+        }
+
+        let mut bytes = vec![0u8; self.fields * TEXT_BYTES_PER_FIELD];
+        bytes[..LENGTH_PREFIX_SIZE].copy_from_slice(&(text.len() as u16).to_le_bytes());
+        bytes[LENGTH_PREFIX_SIZE..LENGTH_PREFIX_SIZE + text.len()].copy_from_slice(text.as_bytes());
+
+        bytes
+            .chunks(TEXT_BYTES_PER_FIELD)
+            .map(|chunk| {
+                let bits: Vec<bool> = chunk.iter().flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1)).collect();
+                Ok(Field::<N>::from_bits_le(&bits)?)
+            })
+            .collect()
+    }
+
+    /// Decodes the text, rejecting fields that `encode` would not have produced.
+    pub fn decode<N: NetworkNative>(&self, fields: &[Field<N>]) -> Result<String, CustomError> {
+        if fields.len() != self.fields {
+            return Err(anyhow!("A text attribute spans {} fields, got {}", self.fields, fields.len()).into());
+        }
+        let text = decode_text(fields)?;
+        let chars = text.chars().count();
+        if chars > self.max_chars {
+            return Err(anyhow!("Text is {} characters long, the maximum is {}", chars, self.max_chars).into());
+        }
+        Ok(text)
+    }
+}
+
+/// Returns the NFC normalization of the text.
+pub fn normalize_text(text: &str) -> String {
+    text.nfc().collect()
+}
+
+/// Decodes text encoded by a `TextAttribute` of any field count.
+pub fn decode_text<N: NetworkNative>(fields: &[Field<N>]) -> Result<String, CustomError> {
+    let mut bytes = Vec::with_capacity(fields.len() * TEXT_BYTES_PER_FIELD);
+    for field in fields {
+        let bits = field.to_bits_le();
+        if bits[TEXT_BYTES_PER_FIELD * 8..].iter().any(|bit| *bit) {
+            return Err(anyhow!("{} is not a text field", field).into());
+        }
+        bytes.extend(bits[..TEXT_BYTES_PER_FIELD * 8].chunks(8).map(|bits| {
+            bits.iter().enumerate().fold(0u8, |byte, (i, bit)| byte | ((*bit as u8) << i))
+        }));
+    }
+    if bytes.len() < LENGTH_PREFIX_SIZE {
+        return Err(anyhow!("A text attribute spans at least one field").into());
+    }
+
+    let length = u16::from_le_bytes([bytes[0], bytes[1]]) as usize;
+    let (text, padding) = bytes[LENGTH_PREFIX_SIZE..]
+        .split_at_checked(length)
+        .ok_or_else(|| anyhow!("Text length {} exceeds the {} encoded bytes", length, bytes.len() - LENGTH_PREFIX_SIZE))?;
+    if padding.iter().any(|byte| *byte != 0) {
+        return Err(anyhow!("Text attribute has data after its end").into());
+    }
+    let text = String::from_utf8(text.to_vec()).map_err(|e| anyhow!("Text attribute is not UTF-8: {}", e))?;
+    if !is_nfc(&text) {
+        return Err(anyhow!("Text attribute is not NFC-normalized").into());
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    #[test]
+    fn test_normalization_forms_encode_equally() {
+        let layout = TextAttribute::new(1, 20).unwrap();
+        let composed = layout.encode::<N>("Jos\u{e9}").unwrap();
+        let decomposed = layout.encode::<N>("Jose\u{301}").unwrap();
+        assert_eq!(composed, decomposed);
+        assert_eq!(layout.decode(&composed).unwrap(), "Jos\u{e9}");
+    }
+
+    #[test]
+    fn test_roundtrip_across_fields() {
+        let layout = TextAttribute::new(3, 80).unwrap();
+        assert_eq!(layout.capacity(), 91);
+        for text in ["", "a", "Ελληνικά", "東京都千代田区千代田1-1", "1 Rue de l'Église, 75001 Paris, France — bâtiment B 🏛"] {
+            let fields = layout.encode::<N>(text).unwrap();
+            assert_eq!(fields.len(), 3);
+            assert_eq!(layout.decode(&fields).unwrap(), text);
+        }
+        assert_ne!(layout.encode::<N>("").unwrap(), layout.encode::<N>("\0").unwrap());
+    }
+
+    #[test]
+    fn test_limits_and_malformed_fields() {
+        let layout = TextAttribute::new(1, 5).unwrap();
+        assert!(layout.encode::<N>("abcdef").is_err());
+        assert!(TextAttribute::new(1, 100).unwrap().encode::<N>(&"a".repeat(30)).is_err());
+        assert!(TextAttribute::new(0, 5).is_err());
+        assert!(TextAttribute::new(usize::MAX / TEXT_BYTES_PER_FIELD + 1, 5).is_err());
+        assert!(TextAttribute::new(2115, 5).is_err());
+        assert_eq!(TextAttribute::new(2114, 5).unwrap().capacity(), 2114 * TEXT_BYTES_PER_FIELD - 2);
+        assert!(serde_json::from_str::<TextAttribute>(&format!(r#"{{"fields":{},"max_chars":5}}"#, usize::MAX)).is_err());
+
+        let fields = TextAttribute::new(2, 50).unwrap().encode::<N>("hello").unwrap();
+        assert!(layout.decode(&fields).is_err());
+        assert!(decode_text::<N>(&[Field::<N>::from_u8(40)]).is_err());
+        assert!(decode_text::<N>(&[-Field::<N>::from_u8(1)]).is_err());
+    }
+}
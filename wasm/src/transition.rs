@@ -0,0 +1,256 @@
+use super::*;
+use crate::program::literal_from_json;
+
+/// The credential struct definitions of the `verify_poseidon2_zpass` and `zpass_invalidate` programs,
+/// checked against their Leo sources by `test_structs_match_leo_sources`.
+const ZPASS_STRUCTS: &str = "
+struct PrivateCredentials:
+    issuer as address;
+    subject as address;
+    dob as u32;
+    nationality as field;
+    expiry as u32;
+
+struct PublicCredentials:
+    salt as scalar;
+
+struct FullCredentials:
+    issuer as address;
+    subject as address;
+    dob as u32;
+    nationality as field;
+    expiry as u32;
+    salt as scalar;
+";
+
+/// The credential struct definitions of the `zpass_hiding` and `zpass_invalidate_hiding` programs,
+/// checked against their Leo sources by `test_structs_match_leo_sources`.
+const HIDING_STRUCTS: &str = "
+struct PrivateCredential:
+    issuer as address;
+    subject as address;
+    dob as u32;
+    nationality as field;
+    expiry as u32;
+
+struct PublicCredential:
+    salt as scalar;
+";
+
+/// An input of a transition, in declaration order.
+#[derive(Debug, Clone, Copy)]
+enum TransitionInput {
+    /// The issuer signature over the credential hash.
+    Signature,
+    /// A credential attribute passed as a literal of the given type.
+    Attribute(&'static str, LiteralType),
+    /// A struct built out of the credential attributes and the salt.
+    Struct(&'static str),
+}
+
+/// The inputs of a transition of a zPass program variant.
+struct TransitionSpec {
+    program: &'static str,
+    transition: &'static str,
+    /// The struct definitions, if the transition takes struct inputs.
+    structs: Option<&'static str>,
+    inputs: &'static [TransitionInput],
+}
+
+const ISSUE_INPUTS: &[TransitionInput] = &[
+    TransitionInput::Signature,
+    TransitionInput::Struct("PrivateCredentials"),
+    TransitionInput::Struct("PublicCredentials"),
+];
+
+const ISSUE_HIDING_INPUTS: &[TransitionInput] = &[
+    TransitionInput::Signature,
+    TransitionInput::Struct("PrivateCredential"),
+    TransitionInput::Struct("PublicCredential"),
+];
+
+/// The transitions taking credential attributes as inputs.
+const TRANSITIONS: &[TransitionSpec] = &[
+    TransitionSpec {
+        program: "verify_poseidon2",
+        transition: "verify",
+        structs: None,
+        // The subject is `self.caller`, so it is not an input.
+        inputs: &[
+            TransitionInput::Signature,
+            TransitionInput::Attribute(ISSUER_ATTRIBUTE, LiteralType::Address),
+            TransitionInput::Attribute("dob", LiteralType::U32),
+            TransitionInput::Attribute("nationality", LiteralType::Field),
+            TransitionInput::Attribute("expiry", LiteralType::U32),
+        ],
+    },
+    TransitionSpec { program: "verify_poseidon2_zpass", transition: "issue", structs: Some(ZPASS_STRUCTS), inputs: ISSUE_INPUTS },
+    TransitionSpec { program: "zpass_hiding", transition: "issue", structs: Some(HIDING_STRUCTS), inputs: ISSUE_HIDING_INPUTS },
+    TransitionSpec { program: "zpass_invalidate", transition: "issue", structs: Some(ZPASS_STRUCTS), inputs: ISSUE_INPUTS },
+    TransitionSpec { program: "zpass_invalidate", transition: "is_invalid", structs: Some(ZPASS_STRUCTS), inputs: &[TransitionInput::Struct("FullCredentials")] },
+    TransitionSpec { program: "zpass_invalidate_hiding", transition: "issue", structs: Some(HIDING_STRUCTS), inputs: ISSUE_HIDING_INPUTS },
+];
+
+/// Returns the supported transitions as `program/transition`, e.g. `verify_poseidon2_zpass/issue`.
+pub fn supported_transitions() -> Vec<String> {
+    TRANSITIONS.iter().map(|spec| format!("{}/{}", spec.program, spec.transition)).collect()
+}
+
+/// Generates the ordered input strings of a transition of a zPass program variant, such as the
+/// `sig`, `pri` and `pub` inputs of `verify_poseidon2_zpass.aleo/issue`.
+///
+/// `program` may be given with or without its `.aleo` suffix. Struct inputs take the members they
+/// declare from the credential attributes and `salt`, type-checked as in `ProgramStructs`, while
+/// attributes the program fills in itself, like the `subject` of `verify_poseidon2/verify`, are
+/// left out. The `salt` is only required by transitions that take it.
+pub fn transition_inputs<N: NetworkNative>(program: &str, transition: &str, data: &JsonValue, signature: &str, salt: Option<&str>) -> Result<Vec<String>, CustomError> {
+    let program = program.trim_end_matches(".aleo");
+    let spec = TRANSITIONS
+        .iter()
+        .find(|spec| spec.program == program && spec.transition == transition)
+        .ok_or_else(|| anyhow!("Unsupported transition '{}/{}', expected one of: {}", program, transition, supported_transitions().join(", ")))?;
+
+    let attributes = with_salt(data, salt)?;
+    let structs = spec.structs
+        .map(|structs| ProgramStructs::<N>::from_source(&format!("program {}.aleo;\n{}", spec.program, structs)))
+        .transpose()?;
+
+    spec.inputs
+        .iter()
+        .map(|input| match input {
+            TransitionInput::Signature => Signature::<N>::from_str(signature)
+                .map(|signature| signature.to_string())
+                .map_err(|e| anyhow!("Invalid signature '{}': {}", signature, e).into()),
+            TransitionInput::Attribute(name, literal_type) => {
+                let value = attributes.get(*name).ok_or_else(|| anyhow!("Missing '{}' of type '{}'", name, literal_type))?;
+                literal_from_json::<N>(*literal_type, value, name).map(|literal| literal.to_string())
+            }
+            TransitionInput::Struct(name) => {
+                let structs = structs.as_ref().ok_or_else(|| anyhow!("'{}/{}' defines no structs", spec.program, spec.transition))?;
+                struct_from_attributes(structs, name, &attributes).map(|plaintext| plaintext.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Builds the `FullCredentials` struct the `zpass_invalidate` programs commit to, out of the
+/// credential attributes, including the subject, and the salt.
+pub(crate) fn full_credentials<N: NetworkNative>(data: &JsonValue, salt: &Scalar<N>) -> Result<Plaintext<N>, CustomError> {
+    let structs = ProgramStructs::<N>::from_source(&format!("program zpass_invalidate.aleo;\n{}", ZPASS_STRUCTS))?;
+    struct_from_attributes(&structs, "FullCredentials", &with_salt(data, Some(&salt.to_string()))?)
+}
+
+// Returns the credential attributes, with the salt added if given.
+fn with_salt(data: &JsonValue, salt: Option<&str>) -> Result<serde_json::Map<String, JsonValue>, CustomError> {
+    let mut attributes = data.as_object()
+        .ok_or_else(|| anyhow!("Credential data must be a JSON object"))?
+        .clone();
+    if let Some(salt) = salt {
+        attributes.insert("salt".to_string(), JsonValue::String(salt.to_string()));
+    }
+    Ok(attributes)
+}
+
+// Builds the struct out of the attributes, taking only the members the struct declares.
+fn struct_from_attributes<N: NetworkNative>(structs: &ProgramStructs<N>, name: &str, attributes: &serde_json::Map<String, JsonValue>) -> Result<Plaintext<N>, CustomError> {
+    let members = structs.get(name)?
+        .members()
+        .keys()
+        .filter_map(|member| attributes.get_key_value(&member.to_string()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect::<serde_json::Map<_, _>>();
+    structs.to_plaintext(name, &JsonValue::Object(members))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{PRIVATE_KEY, ISSUER, SUBJECT, credentials};
+    use serde_json::json;
+
+    // Define the network type for the tests
+    type N = TestnetV0;
+
+    // Returns the Leo source of the program.
+    fn leo_source(program: &str) -> &'static str {
+        match program {
+            "verify_poseidon2_zpass" => include_str!("../../programs/verify_poseidon2_zpass/src/main.leo"),
+            "zpass_hiding" => include_str!("../../programs/zpass_hiding/src/main.leo"),
+            "zpass_invalidate" => include_str!("../../programs/zpass_invalidate/src/main.leo"),
+            "zpass_invalidate_hiding" => include_str!("../../programs/zpass_invalidate_hiding/src/main.leo"),
+            _ => panic!("No Leo source for '{}'", program),
+        }
+    }
+
+    // Returns the `name: type` members of the Leo struct, in declaration order.
+    fn leo_struct_members(source: &str, name: &str) -> Vec<String> {
+        let start = source.find(&format!("struct {} {{", name)).unwrap_or_else(|| panic!("No struct '{}' in the Leo source", name));
+        let body = &source[start..];
+        let body = &body[body.find('{').unwrap() + 1..body.find('}').unwrap()];
+        body.lines()
+            .map(|line| line.split("//").next().unwrap())
+            .flat_map(|line| line.split(','))
+            .map(|member| member.split_whitespace().collect::<String>())
+            .filter(|member| !member.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn test_structs_match_leo_sources() {
+        for spec in TRANSITIONS {
+            let Some(structs) = spec.structs else { continue };
+            let structs = ProgramStructs::<N>::from_source(&format!("program {}.aleo;\n{}", spec.program, structs)).unwrap();
+            for name in structs.names() {
+                let members: Vec<String> = structs.get(&name).unwrap()
+                    .members()
+                    .iter()
+                    .map(|(member, member_type)| format!("{}:{}", member, member_type))
+                    .collect();
+                assert_eq!(members, leo_struct_members(leo_source(spec.program), &name), "{}/{}", spec.program, name);
+            }
+        }
+    }
+
+    fn signature() -> String {
+        let signer = PrivateKeySigner::<N>::from_str(PRIVATE_KEY).unwrap();
+        signer.sign(&[Field::<N>::from_u8(1)]).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_issue_inputs() {
+        let signature = signature();
+        for program in ["verify_poseidon2_zpass", "zpass_hiding.aleo", "zpass_invalidate", "zpass_invalidate_hiding"] {
+            let inputs = transition_inputs::<N>(program, "issue", &credentials(), &signature, Some("1234scalar")).unwrap();
+            assert_eq!(inputs.len(), 3);
+            assert_eq!(inputs[0], signature);
+            assert_eq!(Plaintext::<N>::from_str(&inputs[1]).unwrap(), Plaintext::<N>::from_str(&format!(
+                "{{ issuer: {}, subject: {}, dob: 20000101u32, nationality: 840field, expiry: 20300101u32 }}", ISSUER, SUBJECT
+            )).unwrap());
+            assert_eq!(Plaintext::<N>::from_str(&inputs[2]).unwrap(), Plaintext::<N>::from_str("{ salt: 1234scalar }").unwrap());
+        }
+    }
+
+    #[test]
+    fn test_caller_attributes_are_left_out() {
+        let inputs = transition_inputs::<N>("verify_poseidon2", "verify", &credentials(), &signature(), None).unwrap();
+        assert_eq!(inputs[1..], [ISSUER, "20000101u32", "840field", "20300101u32"]);
+
+        let inputs = transition_inputs::<N>("zpass_invalidate", "is_invalid", &credentials(), &signature(), Some("1234scalar")).unwrap();
+        assert_eq!(inputs.len(), 1);
+        assert!(inputs[0].contains(SUBJECT));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let signature = signature();
+        assert!(transition_inputs::<N>("zpass_merkle_8", "issue", &credentials(), &signature, None).is_err());
+        assert!(transition_inputs::<N>("verify_poseidon2_zpass", "issue", &credentials(), &signature, None).is_err());
+        assert!(transition_inputs::<N>("verify_poseidon2_zpass", "issue", &credentials(), "sign1invalid", Some("1scalar")).is_err());
+        assert!(transition_inputs::<N>("verify_poseidon2_zpass", "issue", &credentials(), &signature, Some("1field")).is_err());
+
+        let mut data = credentials();
+        data["dob"] = json!("20000101u64");
+        assert!(transition_inputs::<N>("verify_poseidon2_zpass", "issue", &data, &signature, Some("1scalar")).is_err());
+        assert!(transition_inputs::<N>("verify_poseidon2", "verify", &data, &signature, None).is_err());
+    }
+}
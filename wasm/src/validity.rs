@@ -0,0 +1,191 @@
+use super::*;
+
+/// The credential attribute holding the issuance time.
+pub const ISSUED_AT_ATTRIBUTE: &str = "issued_at";
+/// The credential attribute holding the time the credential becomes valid.
+pub const NOT_BEFORE_ATTRIBUTE: &str = "not_before";
+/// The credential attribute holding the last time the credential is valid.
+pub const EXPIRY_ATTRIBUTE: &str = "expiry";
+
+/// How the validity attributes of a credential encode time, as `u32` values.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeBasis {
+    /// Calendar dates encoded as `YYYYMMDD`, e.g. `20300101u32`.
+    Date = 0,
+    /// Aleo block heights.
+    BlockHeight = 1,
+}
+
+impl TimeBasis {
+    /// Checks the value is a valid time in this basis.
+    pub fn validate(&self, value: u32) -> Result<(), CustomError> {
+        match self {
+            TimeBasis::Date if Date::decode(value, DateEncoding::YyyyMmDd).is_err() => Err(CustomError::InvalidValidity(format!("{} is not a YYYYMMDD calendar date", value))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The time credentials are checked against: a date or a block height.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceTime {
+    pub(crate) basis: TimeBasis,
+    pub(crate) value: u32,
+}
+
+impl ReferenceTime {
+    /// Creates a reference date, encoded as `YYYYMMDD`.
+    pub fn date(value: u32) -> Result<Self, CustomError> {
+        Self::try_new(TimeBasis::Date, value)
+    }
+
+    /// Creates a reference block height.
+    pub fn block_height(value: u32) -> Self {
+        Self { basis: TimeBasis::BlockHeight, value }
+    }
+
+    /// Creates the reference date of the unix time (seconds, UTC).
+    pub fn date_of_unix(seconds: u64) -> Result<Self, CustomError> {
+        let date = Date::from_days_since_epoch((seconds / 86_400) as i64)?;
+        Self::date(date.encode(DateEncoding::YyyyMmDd)?)
+    }
+
+    /// Creates the reference date of today (UTC).
+    pub fn today() -> Result<Self, CustomError> {
+        #[cfg(target_arch = "wasm32")]
+        let seconds = (js_sys::Date::now() / 1000.0) as u64;
+        #[cfg(not(target_arch = "wasm32"))]
+        let seconds = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| anyhow!("The system clock is before 1970: {}", e))?
+            .as_secs();
+        Self::date_of_unix(seconds)
+    }
+
+    fn try_new(basis: TimeBasis, value: u32) -> Result<Self, CustomError> {
+        basis.validate(value)?;
+        Ok(Self { basis, value })
+    }
+}
+
+#[wasm_bindgen]
+impl ReferenceTime {
+    /// Constructor for `ReferenceTime`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(basis: TimeBasis, value: u32) -> Result<ReferenceTime, String> {
+        Self::try_new(basis, value).map_err(|e| e.to_string())
+    }
+
+    /// Returns how the time is encoded.
+    #[wasm_bindgen(getter)]
+    pub fn basis(&self) -> TimeBasis {
+        self.basis
+    }
+
+    /// Returns the encoded time.
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// The validity window of a credential, read from its `issued_at`, `not_before` and `expiry`
+/// attributes. Each of them is optional, and the window includes both of its bounds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validity {
+    pub issued_at: Option<u32>,
+    pub not_before: Option<u32>,
+    pub expiry: Option<u32>,
+}
+
+impl Validity {
+    /// Reads the validity attributes of the credential data, which must be `u32` values in `basis`.
+    pub fn from_data(data: &JsonValue, basis: TimeBasis) -> Result<Self, CustomError> {
+        let attributes = data.as_object().ok_or(CustomError::InvalidCredentialData)?;
+        let read = |name: &str| -> Result<Option<u32>, CustomError> {
+            let Some(value) = attributes.get(name) else {
+                return Ok(None);
+            };
+            let value = value.as_str()
+                .and_then(|value| value.strip_suffix("u32"))
+                .and_then(|value| value.parse::<u32>().ok())
+                .ok_or_else(|| CustomError::InvalidValidity(format!("Attribute '{}' must be a u32, got {}", name, value)))?;
+            basis.validate(value)?;
+            Ok(Some(value))
+        };
+        let validity = Self { issued_at: read(ISSUED_AT_ATTRIBUTE)?, not_before: read(NOT_BEFORE_ATTRIBUTE)?, expiry: read(EXPIRY_ATTRIBUTE)? };
+
+        if let Some(expiry) = validity.expiry {
+            if let Some(start) = validity.issued_at.into_iter().chain(validity.not_before).find(|start| *start > expiry) {
+                return Err(CustomError::InvalidValidity(format!("The credential expires at {} before it starts at {}", expiry, start)));
+            }
+        }
+        Ok(validity)
+    }
+
+    /// Returns the earliest time the credential is valid, if any.
+    pub fn start(&self) -> Option<u32> {
+        self.issued_at.into_iter().chain(self.not_before).max()
+    }
+
+    /// Checks the credential has not expired at `time`.
+    pub fn check_not_expired(&self, time: u32) -> Result<(), CustomError> {
+        match self.expiry {
+            Some(expiry) if time > expiry => Err(CustomError::Expired { expiry, time }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks `time` is inside the validity window.
+    pub fn check(&self, time: u32) -> Result<(), CustomError> {
+        match self.start() {
+            Some(start) if time < start => Err(CustomError::NotYetValid { not_before: start, time }),
+            _ => self.check_not_expired(time),
+        }
+    }
+
+    /// Checks the validity window of the credential data at the reference time, if one is given.
+    pub fn check_data(data: &JsonValue, at: Option<ReferenceTime>) -> Result<(), CustomError> {
+        match at {
+            Some(at) => Self::from_data(data, at.basis)?.check(at.value),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validity_window() {
+        let data = json!({ "issued_at": "20240101u32", "not_before": "20240201u32", "expiry": "20300101u32" });
+        let validity = Validity::from_data(&data, TimeBasis::Date).unwrap();
+        assert_eq!(validity.start(), Some(20240201));
+
+        assert!(validity.check(20240201).is_ok());
+        assert!(validity.check(20300101).is_ok());
+        assert!(matches!(validity.check(20240115), Err(CustomError::NotYetValid { not_before: 20240201, time: 20240115 })));
+        assert!(matches!(validity.check(20300102), Err(CustomError::Expired { expiry: 20300101, time: 20300102 })));
+        assert!(validity.check_not_expired(20240115).is_ok());
+
+        let unbounded = Validity::from_data(&json!({ "dob": "20000101u32" }), TimeBasis::Date).unwrap();
+        assert!(unbounded.check(u32::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_validity_encoding() {
+        assert!(Validity::from_data(&json!({ "expiry": "20230229u32" }), TimeBasis::Date).is_err());
+        assert!(Validity::from_data(&json!({ "expiry": "20240229u32" }), TimeBasis::Date).is_ok());
+        assert!(Validity::from_data(&json!({ "expiry": "20240229u32" }), TimeBasis::BlockHeight).is_ok());
+        assert!(Validity::from_data(&json!({ "expiry": "20300101u64" }), TimeBasis::Date).is_err());
+        assert!(Validity::from_data(&json!({ "issued_at": "20300101u32", "expiry": "20200101u32" }), TimeBasis::Date).is_err());
+
+        assert!(ReferenceTime::date(20241301).is_err());
+        assert_eq!(ReferenceTime::block_height(5).value(), 5);
+    }
+}
@@ -22,12 +22,24 @@ pub fn hash_to_fields_size_8(inputs: Vec<String>, network: Network) -> Result<Ve
 
 #[wasm_bindgen]
 pub fn sign_merkle_root(private_key: String, root: String, network: Network) -> Result<String, String> {
+    sign_merkle_root_with_secret(&SecretString::new(private_key), &root, network)
+}
+
+/// Signs the merkle root like `sign_merkle_root`, taking the private key as UTF-8 bytes.
+/// The key buffer is cleared before returning, whether or not signing succeeds.
+#[wasm_bindgen]
+pub fn sign_merkle_root_with_key_bytes(private_key: &mut [u8], root: String, network: Network) -> Result<String, String> {
+    let private_key = SecretString::from_bytes(private_key).map_err(|e| e.to_string())?;
+    sign_merkle_root_with_secret(&private_key, &root, network)
+}
+
+fn sign_merkle_root_with_secret(private_key: &SecretString, root: &str, network: Network) -> Result<String, String> {
     match network {
-        Network::Testnet => sign_root::<TestnetV0>(&private_key, &root)
-            .map(|signature| signature.to_string())
+        Network::Testnet => PrivateKeySigner::<TestnetV0>::from_secret(private_key)
+            .and_then(|signer| sign_root::<TestnetV0>(&signer, root, &ConsoleLogger))
             .map_err(|e| e.to_string()),
-        Network::Mainnet => sign_root::<MainnetV0>(&private_key, &root)
-            .map(|signature| signature.to_string())
+        Network::Mainnet => PrivateKeySigner::<MainnetV0>::from_secret(private_key)
+            .and_then(|signer| sign_root::<MainnetV0>(&signer, root, &ConsoleLogger))
             .map_err(|e| e.to_string()),
     }
 }
@@ -91,17 +103,66 @@ pub fn get_merkle_tree(inputs: Vec<String>, network: Network) -> Result<JsValue,
 }
 
 /// Exposes a Rust function to JavaScript for signing messages.
+/// The hash is computed as a field unless another `output_type` is given. When `hasher` names a
+/// registered hasher (e.g. `poseidon2` or a scheme registered through `register_hasher`), it is
+/// used instead of `hash_alg`.
 /// Returns the response as `SignResponse` or a `JsValue` error.
 #[wasm_bindgen]
 pub fn sign_message(
     private_key: String,
     message: SignInboundMessage,
     hash_alg: HashAlgorithm,
-    network: Network
+    network: Network,
+    output_type: Option<HashOutputType>,
+    hasher: Option<String>
+) -> Result<SignResponse, JsValue> {
+    sign_message_with_secret(&SecretString::new(private_key), message, hash_alg, network, output_type, hasher.as_deref(), &SignChecks::default())
+}
+
+/// Signs the message like `sign_message`, running the given pre-sign `checks` instead of the default ones.
+#[wasm_bindgen]
+pub fn sign_message_checked(
+    private_key: String,
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>,
+    hasher: Option<String>,
+    checks: &SignChecks
+) -> Result<SignResponse, JsValue> {
+    sign_message_with_secret(&SecretString::new(private_key), message, hash_alg, network, output_type, hasher.as_deref(), checks)
+}
+
+/// Signs the message like `sign_message`, taking the private key as UTF-8 bytes.
+/// The key buffer is cleared before returning, whether or not signing succeeds.
+#[wasm_bindgen]
+pub fn sign_message_with_key_bytes(
+    private_key: &mut [u8],
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>,
+    hasher: Option<String>
+) -> Result<SignResponse, JsValue> {
+    let private_key = SecretString::from_bytes(private_key)
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+    sign_message_with_secret(&private_key, message, hash_alg, network, output_type, hasher.as_deref(), &SignChecks::default())
+}
+
+fn sign_message_with_secret(
+    private_key: &SecretString,
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>,
+    hasher: Option<&str>,
+    checks: &SignChecks
 ) -> Result<SignResponse, JsValue> {
     let result = match network {
-        Network::Testnet => sign_message_with_logger::<TestnetV0>(private_key, message, hash_alg, &ConsoleLogger),
-        Network::Mainnet => sign_message_with_logger::<MainnetV0>(private_key, message, hash_alg, &ConsoleLogger),
+        Network::Testnet => PrivateKeySigner::<TestnetV0>::from_secret(private_key)
+            .and_then(|signer| sign_message_impl::<TestnetV0>(&signer, message, hash_alg, output_type, hasher, checks)),
+        Network::Mainnet => PrivateKeySigner::<MainnetV0>::from_secret(private_key)
+            .and_then(|signer| sign_message_impl::<MainnetV0>(&signer, message, hash_alg, output_type, hasher, checks)),
     };
 
     result
@@ -109,6 +170,59 @@ pub fn sign_message(
         .map_err(|err| JsValue::from_str(&err.to_string()))
 }
 
+// Signs with the named hasher if one is given, otherwise with `hash_alg` and `output_type`.
+fn sign_message_impl<N: NetworkNative>(
+    signer: &dyn CredentialSigner<N>,
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    output_type: Option<HashOutputType>,
+    hasher: Option<&str>,
+    checks: &SignChecks
+) -> Result<(String, String), CustomError> {
+    match (hasher, output_type) {
+        (None, output_type) => sign_message_with_checks::<N>(signer, message, hash_alg, output_type.unwrap_or_default(), checks, &ConsoleLogger),
+        (Some(_), Some(output_type)) if output_type != HashOutputType::Field => {
+            Err(anyhow!("Named hashers output fields, '{:?}' output is not supported", output_type).into())
+        }
+        (Some(name), _) => sign_message_with_hasher::<N>(signer, message, get_hasher::<N>(name)?.as_ref(), checks, &ConsoleLogger),
+    }
+}
+
+/// Exposes a Rust function to JavaScript for signing messages with a remote issuer signer.
+/// `signer_rpc` is a synchronous `(request: string) => string` function forwarding the signer
+/// JSON-RPC requests (see `RemoteSigner`), so the issuer key never enters the wasm memory.
+/// The hash is selected by `hash_alg`, `output_type` and `hasher` as in `sign_message`.
+/// Returns the response as `SignResponse` or a `JsValue` error.
+#[wasm_bindgen]
+pub fn sign_message_with_remote_signer(
+    signer_rpc: js_sys::Function,
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>,
+    hasher: Option<String>
+) -> Result<SignResponse, JsValue> {
+    let transport = JsFunctionTransport::new(signer_rpc);
+    let checks = SignChecks::default();
+    let result = match network {
+        Network::Testnet => sign_message_impl::<TestnetV0>(&RemoteSigner::new(transport), message, hash_alg, output_type, hasher.as_deref(), &checks),
+        Network::Mainnet => sign_message_impl::<MainnetV0>(&RemoteSigner::new(transport), message, hash_alg, output_type, hasher.as_deref(), &checks),
+    };
+
+    result
+        .map(|(signature, hash)| SignResponse::new(signature, hash))
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Exposes a Rust function to JavaScript listing the names of the registered hashers.
+#[wasm_bindgen]
+pub fn get_hasher_names(network: Network) -> Vec<String> {
+    match network {
+        Network::Testnet => with_global_registry::<TestnetV0, _>(|registry| registry.names()),
+        Network::Mainnet => with_global_registry::<MainnetV0, _>(|registry| registry.names()),
+    }
+}
+
 /// A struct representing the response of a signing operation.
 #[wasm_bindgen]
 pub struct SignResponse {
@@ -160,6 +274,49 @@ pub enum HashAlgorithm {
     KECCAK256 = 3
 }
 
+/// An enum representing the output types a hash can be mapped to, mirroring Leo's `hash_to_*` family.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashOutputType {
+    #[default]
+    Field = 0,
+    Group = 1,
+    Scalar = 2,
+    Address = 3,
+    U8 = 4,
+    U16 = 5,
+    U32 = 6,
+    U64 = 7,
+    U128 = 8,
+    I8 = 9,
+    I16 = 10,
+    I32 = 11,
+    I64 = 12,
+    I128 = 13
+}
+
+impl HashOutputType {
+    /// Returns the literal type the hash is cast to.
+    pub fn literal_type(&self) -> LiteralType {
+        match self {
+            HashOutputType::Field => LiteralType::Field,
+            HashOutputType::Group => LiteralType::Group,
+            HashOutputType::Scalar => LiteralType::Scalar,
+            HashOutputType::Address => LiteralType::Address,
+            HashOutputType::U8 => LiteralType::U8,
+            HashOutputType::U16 => LiteralType::U16,
+            HashOutputType::U32 => LiteralType::U32,
+            HashOutputType::U64 => LiteralType::U64,
+            HashOutputType::U128 => LiteralType::U128,
+            HashOutputType::I8 => LiteralType::I8,
+            HashOutputType::I16 => LiteralType::I16,
+            HashOutputType::I32 => LiteralType::I32,
+            HashOutputType::I64 => LiteralType::I64,
+            HashOutputType::I128 => LiteralType::I128,
+        }
+    }
+}
+
 /// A struct representing the message to be signed in.
 #[wasm_bindgen]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -200,6 +357,25 @@ pub fn get_field_from_value(
     Ok(field)
 }
 
+/// Exposes a Rust function to JavaScript for hashing a value string (e.g. `123field` or a struct literal)
+/// exactly as Leo's `X::hash_to_T(value)` would.
+#[wasm_bindgen]
+pub fn hash_value(
+    value: &str,
+    hash_alg: HashAlgorithm,
+    output_type: HashOutputType,
+    network: Network
+) -> Result<String, String> {
+    match network {
+        Network::Testnet => Value::<TestnetV0>::from_str(value)
+            .and_then(|value| create_hash(value, hash_alg, output_type))
+            .map_err(|e| e.to_string()),
+        Network::Mainnet => Value::<MainnetV0>::from_str(value)
+            .and_then(|value| create_hash(value, hash_alg, output_type))
+            .map_err(|e| e.to_string()),
+    }
+}
+
 macro_rules! verify_credential_impl {
     ($signature:expr, $address:expr, $message:expr, $network:ty) => {{
         let (_, signature_bytes) = Signature::<$network>::parse($signature).unwrap();
@@ -224,3 +400,1033 @@ pub fn verify_signed_credential(
         Network::Mainnet => verify_credential_impl!(signature, address, message, MainnetV0),
     }
 }
+
+/// A struct representing the result of a credential verification.
+#[wasm_bindgen]
+pub struct VerifyResponse {
+    pub(crate) verified: bool,
+    pub(crate) hash: Option<String>,
+    pub(crate) reason: Option<String>,
+}
+
+impl VerifyResponse {
+    /// Creates a `VerifyResponse` from the outcome of `verify_credential_with_logger`.
+    pub fn from_result(result: Result<(bool, String), CustomError>) -> Self {
+        match result {
+            Ok((true, hash)) => VerifyResponse { verified: true, hash: Some(hash), reason: None },
+            Ok((false, hash)) => VerifyResponse {
+                verified: false,
+                hash: Some(hash),
+                reason: Some("Signature does not match the issuer and the recomputed hash".to_string()),
+            },
+            Err(err) => VerifyResponse { verified: false, hash: None, reason: Some(err.to_string()) },
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl VerifyResponse {
+    /// Returns whether the signature is valid for the credential.
+    #[wasm_bindgen(getter)]
+    pub fn verified(&self) -> bool {
+        self.verified
+    }
+
+    /// Returns the recomputed credential hash, if the credential could be hashed.
+    #[wasm_bindgen(getter)]
+    pub fn hash(&self) -> Option<String> {
+        self.hash.clone()
+    }
+
+    /// Returns why the verification failed.
+    #[wasm_bindgen(getter)]
+    pub fn reason(&self) -> Option<String> {
+        self.reason.clone()
+    }
+}
+
+fn verify_credential_attributes<N: NetworkNative>(
+    message: SignInboundMessage,
+    signature: &str,
+    issuer: &str,
+    hash_alg: HashAlgorithm,
+    output_type: HashOutputType,
+    at: Option<ReferenceTime>
+) -> Result<(bool, String), CustomError> {
+    let signature = Signature::<N>::from_str(signature)
+        .map_err(|e| anyhow!("Failed to parse signature: {}", e))?;
+    let issuer = parse_address_normalized::<N>(issuer)?;
+    verify_credential_with_logger::<N>(message, &signature, &issuer, hash_alg, output_type, at, &ConsoleLogger)
+}
+
+/// Exposes a Rust function to JavaScript for verifying an issuer signature from the credential
+/// attributes, hashing them the same way `sign_message` does. When `reference_time` is given,
+/// credentials outside their validity window at that time are rejected.
+#[wasm_bindgen]
+pub fn verify_credential(
+    data: SignInboundMessage,
+    signature: &str,
+    issuer: &str,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>,
+    reference_time: Option<ReferenceTime>
+) -> VerifyResponse {
+    let output_type = output_type.unwrap_or_default();
+    VerifyResponse::from_result(match network {
+        Network::Testnet => verify_credential_attributes::<TestnetV0>(data, signature, issuer, hash_alg, output_type, reference_time),
+        Network::Mainnet => verify_credential_attributes::<MainnetV0>(data, signature, issuer, hash_alg, output_type, reference_time),
+    })
+}
+
+fn get_verification_report_impl<N: NetworkNative>(
+    message: SignInboundMessage,
+    signature: &str,
+    issuer: &str,
+    hash_alg: HashAlgorithm,
+    output_type: HashOutputType,
+    options: JsonValue
+) -> Result<String, CustomError> {
+    let signature = Signature::<N>::from_str(signature)
+        .map_err(|e| anyhow!("Failed to parse signature: {}", e))?;
+    let issuer = parse_address_normalized::<N>(issuer)?;
+    let options = VerificationOptions::<N>::from_json(options)?;
+    verify_credential_report(message, &signature, &issuer, hash_alg, output_type, &options, &ConsoleLogger).to_json()
+}
+
+/// Exposes a Rust function to JavaScript for verifying a credential with a per-check report.
+/// `options` holds the optional checks (see `VerificationOptions::from_json`); pass `{}` to only
+/// check the signature. Returns the `VerificationReport` as JSON.
+#[wasm_bindgen]
+pub fn get_verification_report(
+    data: SignInboundMessage,
+    signature: &str,
+    issuer: &str,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>,
+    options: JsValue
+) -> Result<String, String> {
+    let output_type = output_type.unwrap_or_default();
+    let options: JsonValue = if options.is_undefined() || options.is_null() {
+        JsonValue::Object(Default::default())
+    } else {
+        serde_wasm_bindgen::from_value(options).map_err(|e| format!("Failed to parse options: {}", e))?
+    };
+    match network {
+        Network::Testnet => get_verification_report_impl::<TestnetV0>(data, signature, issuer, hash_alg, output_type, options),
+        Network::Mainnet => get_verification_report_impl::<MainnetV0>(data, signature, issuer, hash_alg, output_type, options),
+    }.map_err(|e| e.to_string())
+}
+
+enum IssuerSignerInner {
+    Testnet(PrivateKeySigner<TestnetV0>),
+    Mainnet(PrivateKeySigner<MainnetV0>),
+}
+
+/// A handle to an unlocked issuer key.
+/// Signs on behalf of the issuer without handing the private key back to JavaScript.
+#[wasm_bindgen]
+pub struct IssuerSigner {
+    inner: IssuerSignerInner,
+}
+
+#[wasm_bindgen]
+impl IssuerSigner {
+    /// Returns the issuer address.
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> Result<String, String> {
+        match &self.inner {
+            IssuerSignerInner::Testnet(signer) => signer.address().map(|address| address.to_string()),
+            IssuerSignerInner::Mainnet(signer) => signer.address().map(|address| address.to_string()),
+        }.map_err(|e| e.to_string())
+    }
+
+    /// Signs the message like `sign_message`, with the unlocked issuer key.
+    pub fn sign_message(
+        &self,
+        message: SignInboundMessage,
+        hash_alg: HashAlgorithm,
+        output_type: Option<HashOutputType>
+    ) -> Result<SignResponse, JsValue> {
+        let output_type = output_type.unwrap_or_default();
+        let result = match &self.inner {
+            IssuerSignerInner::Testnet(signer) => sign_message_with_logger::<TestnetV0>(signer, message, hash_alg, output_type, &ConsoleLogger),
+            IssuerSignerInner::Mainnet(signer) => sign_message_with_logger::<MainnetV0>(signer, message, hash_alg, output_type, &ConsoleLogger),
+        };
+
+        result
+            .map(|(signature, hash)| SignResponse::new(signature, hash))
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    /// Signs the merkle root like `sign_merkle_root`, with the unlocked issuer key.
+    pub fn sign_merkle_root(&self, root: String) -> Result<String, String> {
+        match &self.inner {
+            IssuerSignerInner::Testnet(signer) => sign_root::<TestnetV0>(signer, &root, &ConsoleLogger),
+            IssuerSignerInner::Mainnet(signer) => sign_root::<MainnetV0>(signer, &root, &ConsoleLogger),
+        }.map_err(|e| e.to_string())
+    }
+}
+
+/// Exposes a Rust function to JavaScript for encrypting an issuer private key into a keystore JSON file.
+#[wasm_bindgen]
+pub fn encrypt_keystore(private_key: String, passphrase: &str, network: Network) -> Result<String, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => SecretPrivateKey::<TestnetV0>::from_secret(&private_key)
+            .and_then(|private_key| Keystore::encrypt(private_key.expose(), passphrase))
+            .and_then(|keystore| keystore.to_json()),
+        Network::Mainnet => SecretPrivateKey::<MainnetV0>::from_secret(&private_key)
+            .and_then(|private_key| Keystore::encrypt(private_key.expose(), passphrase))
+            .and_then(|keystore| keystore.to_json()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for unlocking a keystore JSON file into an `IssuerSigner`.
+#[wasm_bindgen]
+pub fn unlock_keystore(keystore: &str, passphrase: &str, network: Network) -> Result<IssuerSigner, String> {
+    let inner = match network {
+        Network::Testnet => Keystore::<TestnetV0>::from_json(keystore)
+            .and_then(|keystore| keystore.unlock(passphrase))
+            .map(IssuerSignerInner::Testnet),
+        Network::Mainnet => Keystore::<MainnetV0>::from_json(keystore)
+            .and_then(|keystore| keystore.unlock(passphrase))
+            .map(IssuerSignerInner::Mainnet),
+    }.map_err(|e| e.to_string())?;
+    Ok(IssuerSigner { inner })
+}
+
+/// Exposes a Rust function to JavaScript for re-encrypting a keystore JSON file under a new passphrase.
+#[wasm_bindgen]
+pub fn change_keystore_passphrase(keystore: &str, old_passphrase: &str, new_passphrase: &str, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => Keystore::<TestnetV0>::from_json(keystore)
+            .and_then(|keystore| keystore.change_passphrase(old_passphrase, new_passphrase))
+            .and_then(|keystore| keystore.to_json()),
+        Network::Mainnet => Keystore::<MainnetV0>::from_json(keystore)
+            .and_then(|keystore| keystore.change_passphrase(old_passphrase, new_passphrase))
+            .and_then(|keystore| keystore.to_json()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for reading the issuer address of a keystore JSON file.
+#[wasm_bindgen]
+pub fn get_keystore_address(keystore: &str, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => Keystore::<TestnetV0>::from_json(keystore).map(|keystore| keystore.address().to_string()),
+        Network::Mainnet => Keystore::<MainnetV0>::from_json(keystore).map(|keystore| keystore.address().to_string()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for splitting an issuer private key into `shares` key shares,
+/// any `threshold` of which recover it. Each share is returned as a JSON string.
+#[wasm_bindgen]
+pub fn split_issuer_key(private_key: String, threshold: u8, shares: u8, network: Network) -> Result<Vec<String>, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => SecretPrivateKey::<TestnetV0>::from_secret(&private_key)
+            .and_then(|private_key| split_private_key(private_key.expose(), threshold, shares)),
+        Network::Mainnet => SecretPrivateKey::<MainnetV0>::from_secret(&private_key)
+            .and_then(|private_key| split_private_key(private_key.expose(), threshold, shares)),
+    }
+    .map(|shares| shares.iter().map(|share| share.to_string()).collect())
+    .map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for recovering an issuer private key from its key shares.
+#[wasm_bindgen]
+pub fn recover_issuer_key(shares: Vec<String>, network: Network) -> Result<String, String> {
+    let shares = shares.iter()
+        .map(|share| KeyShare::from_str(share))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    match network {
+        Network::Testnet => recover_private_key::<TestnetV0>(&shares).map(|private_key| private_key.expose().to_string()),
+        Network::Mainnet => recover_private_key::<MainnetV0>(&shares).map(|private_key| private_key.expose().to_string()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for recovering an issuer key from its key shares straight into an
+/// `IssuerSigner`, without handing the private key back to JavaScript.
+#[wasm_bindgen]
+pub fn recover_issuer_signer(shares: Vec<String>, network: Network) -> Result<IssuerSigner, String> {
+    let shares = shares.iter()
+        .map(|share| KeyShare::from_str(share))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    let inner = match network {
+        Network::Testnet => recover_private_key::<TestnetV0>(&shares)
+            .and_then(PrivateKeySigner::new)
+            .map(IssuerSignerInner::Testnet),
+        Network::Mainnet => recover_private_key::<MainnetV0>(&shares)
+            .and_then(PrivateKeySigner::new)
+            .map(IssuerSignerInner::Mainnet),
+    }.map_err(|e| e.to_string())?;
+    Ok(IssuerSigner { inner })
+}
+
+/// A struct representing an issuer account: private key, view key and address.
+#[wasm_bindgen]
+pub struct AccountResponse {
+    pub(crate) private_key: String,
+    pub(crate) view_key: String,
+    pub(crate) address: String,
+}
+
+impl AccountResponse {
+    /// Creates a new instance of `AccountResponse` from a Rust account.
+    pub fn from_account<N: NetworkNative>(account: &IssuerAccount<N>) -> Self {
+        AccountResponse {
+            private_key: account.private_key().expose().to_string(),
+            view_key: account.view_key().to_string(),
+            address: account.address().to_string(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl AccountResponse {
+    /// Returns the private key of the account.
+    #[wasm_bindgen(getter)]
+    pub fn private_key(&self) -> String {
+        self.private_key.clone()
+    }
+
+    /// Returns the view key of the account.
+    #[wasm_bindgen(getter)]
+    pub fn view_key(&self) -> String {
+        self.view_key.clone()
+    }
+
+    /// Returns the address of the account.
+    #[wasm_bindgen(getter)]
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+}
+
+/// Exposes a Rust function to JavaScript for generating a new English BIP-39 mnemonic.
+#[wasm_bindgen]
+pub fn new_issuer_mnemonic(word_count: usize) -> Result<String, String> {
+    generate_mnemonic(word_count)
+        .map(|phrase| phrase.expose().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for checking that a phrase is a valid BIP-39 mnemonic.
+#[wasm_bindgen]
+pub fn is_valid_mnemonic(phrase: &str) -> bool {
+    validate_mnemonic(phrase).is_ok()
+}
+
+/// Exposes a Rust function to JavaScript for deriving the issuer account at `index` from a mnemonic.
+#[wasm_bindgen]
+pub fn derive_issuer_account_from_mnemonic(
+    phrase: String,
+    passphrase: Option<String>,
+    index: u32,
+    network: Network
+) -> Result<AccountResponse, String> {
+    let phrase = SecretString::new(phrase);
+    let passphrase = SecretString::new(passphrase.unwrap_or_default());
+    match network {
+        Network::Testnet => derive_issuer_account::<TestnetV0>(phrase.expose(), passphrase.expose(), index)
+            .map(|account| AccountResponse::from_account(&account)),
+        Network::Mainnet => derive_issuer_account::<MainnetV0>(phrase.expose(), passphrase.expose(), index)
+            .map(|account| AccountResponse::from_account(&account)),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for deriving the issuer account at `index` from a mnemonic
+/// straight into an `IssuerSigner`, without handing the private key back to JavaScript.
+#[wasm_bindgen]
+pub fn derive_issuer_signer_from_mnemonic(
+    phrase: String,
+    passphrase: Option<String>,
+    index: u32,
+    network: Network
+) -> Result<IssuerSigner, String> {
+    let phrase = SecretString::new(phrase);
+    let passphrase = SecretString::new(passphrase.unwrap_or_default());
+    let inner = match network {
+        Network::Testnet => derive_issuer_account::<TestnetV0>(phrase.expose(), passphrase.expose(), index)
+            .and_then(|account| account.into_signer())
+            .map(IssuerSignerInner::Testnet),
+        Network::Mainnet => derive_issuer_account::<MainnetV0>(phrase.expose(), passphrase.expose(), index)
+            .and_then(|account| account.into_signer())
+            .map(IssuerSignerInner::Mainnet),
+    }.map_err(|e| e.to_string())?;
+    Ok(IssuerSigner { inner })
+}
+
+/// Exposes a Rust function to JavaScript for generating a new random issuer account.
+#[wasm_bindgen]
+pub fn new_issuer_account(network: Network) -> Result<AccountResponse, String> {
+    match network {
+        Network::Testnet => IssuerAccount::<TestnetV0>::generate().map(|account| AccountResponse::from_account(&account)),
+        Network::Mainnet => IssuerAccount::<MainnetV0>::generate().map(|account| AccountResponse::from_account(&account)),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for deriving the address of a private key.
+#[wasm_bindgen]
+pub fn get_address_from_private_key(private_key: String, network: Network) -> Result<String, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => IssuerAccount::<TestnetV0>::from_secret(&private_key).map(|account| account.address().to_string()),
+        Network::Mainnet => IssuerAccount::<MainnetV0>::from_secret(&private_key).map(|account| account.address().to_string()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for deriving the view key of a private key.
+#[wasm_bindgen]
+pub fn get_view_key_from_private_key(private_key: String, network: Network) -> Result<String, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => IssuerAccount::<TestnetV0>::from_secret(&private_key).map(|account| account.view_key().to_string()),
+        Network::Mainnet => IssuerAccount::<MainnetV0>::from_secret(&private_key).map(|account| account.view_key().to_string()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for checking that a string is a valid address.
+#[wasm_bindgen]
+pub fn is_valid_address(address: &str, network: Network) -> bool {
+    match network {
+        Network::Testnet => Address::<TestnetV0>::from_str(address).is_ok(),
+        Network::Mainnet => Address::<MainnetV0>::from_str(address).is_ok(),
+    }
+}
+
+/// Exposes a Rust function to JavaScript for validating an address and returning its canonical form,
+/// trimmed and lowercased.
+#[wasm_bindgen]
+pub fn normalize_address(address: &str, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => parse_address_normalized::<TestnetV0>(address).map(|address| address.to_string()),
+        Network::Mainnet => parse_address_normalized::<MainnetV0>(address).map(|address| address.to_string()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for checking that an address belongs to a private key.
+#[wasm_bindgen]
+pub fn address_matches_key(address: &str, private_key: String, network: Network) -> Result<bool, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => parse_address_normalized::<TestnetV0>(address)
+            .and_then(|address| IssuerAccount::<TestnetV0>::from_secret(&private_key).map(|account| account.address() == address)),
+        Network::Mainnet => parse_address_normalized::<MainnetV0>(address)
+            .and_then(|address| IssuerAccount::<MainnetV0>::from_secret(&private_key).map(|account| account.address() == address)),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for computing the credential hash that issuers sign.
+/// The hash is computed as a field unless another `output_type` is given.
+#[wasm_bindgen]
+pub fn get_credential_hash(
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>
+) -> Result<String, String> {
+    let output_type = output_type.unwrap_or_default();
+    match network {
+        Network::Testnet => hash_credential::<TestnetV0>(message, hash_alg, output_type, &ConsoleLogger),
+        Network::Mainnet => hash_credential::<MainnetV0>(message, hash_alg, output_type, &ConsoleLogger),
+    }.map_err(|e| e.to_string())
+}
+
+fn issuer_policy_impl<N: NetworkNative>(threshold: usize, issuers: &[String]) -> Result<IssuerPolicy<N>, CustomError> {
+    let issuers = issuers
+        .iter()
+        .map(|issuer| parse_address_normalized::<N>(issuer))
+        .collect::<Result<Vec<_>, _>>()?;
+    IssuerPolicy::new(threshold, issuers)
+}
+
+fn new_multi_issuer_credential_impl<N: NetworkNative>(hash: &str, threshold: usize, issuers: &[String]) -> Result<String, CustomError> {
+    Ok(MultiIssuerCredential::new(hash, issuer_policy_impl::<N>(threshold, issuers)?)?.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for starting a credential that `threshold` of the listed
+/// issuers must sign. Returns the credential as JSON.
+#[wasm_bindgen]
+pub fn new_multi_issuer_credential(hash: &str, threshold: usize, issuers: Vec<String>, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => new_multi_issuer_credential_impl::<TestnetV0>(hash, threshold, &issuers),
+        Network::Mainnet => new_multi_issuer_credential_impl::<MainnetV0>(hash, threshold, &issuers),
+    }.map_err(|e| e.to_string())
+}
+
+fn sign_multi_issuer_credential_impl<N: NetworkNative>(credential: &str, private_key: &SecretString) -> Result<String, CustomError> {
+    let mut credential = MultiIssuerCredential::<N>::from_str(credential)?;
+    credential.sign(&PrivateKeySigner::<N>::from_secret(private_key)?)?;
+    Ok(credential.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for adding an issuer's signature to a multi-issuer credential.
+/// Returns the updated credential as JSON.
+#[wasm_bindgen]
+pub fn sign_multi_issuer_credential(credential: &str, private_key: String, network: Network) -> Result<String, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => sign_multi_issuer_credential_impl::<TestnetV0>(credential, &private_key),
+        Network::Mainnet => sign_multi_issuer_credential_impl::<MainnetV0>(credential, &private_key),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for encoding a `threshold`-of-`issuers` policy as JSON, for
+/// verifiers to keep as their trusted policy.
+#[wasm_bindgen]
+pub fn new_issuer_policy(threshold: usize, issuers: Vec<String>, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => issuer_policy_impl::<TestnetV0>(threshold, &issuers).map(|policy| policy.to_string()),
+        Network::Mainnet => issuer_policy_impl::<MainnetV0>(threshold, &issuers).map(|policy| policy.to_string()),
+    }.map_err(|e| e.to_string())
+}
+
+fn verify_multi_issuer_credential_impl<N: NetworkNative>(
+    credential: &str,
+    policy: &str,
+    message: Option<(SignInboundMessage, Option<ReferenceTime>, HashAlgorithm, HashOutputType)>
+) -> Result<(), CustomError> {
+    let credential = MultiIssuerCredential::<N>::from_str(credential)?;
+    let policy = IssuerPolicy::<N>::from_str(policy)?;
+    match message {
+        Some((message, at, hash_alg, output_type)) => credential.verify_attributes(message, &policy, at, hash_alg, output_type, &ConsoleLogger),
+        None => credential.verify(&policy),
+    }
+}
+
+/// Exposes a Rust function to JavaScript for checking that enough issuers of the trusted `policy`
+/// (see `new_issuer_policy`) signed a multi-issuer credential.
+#[wasm_bindgen]
+pub fn verify_multi_issuer_credential(credential: &str, policy: &str, network: Network) -> Result<bool, String> {
+    match network {
+        Network::Testnet => verify_multi_issuer_credential_impl::<TestnetV0>(credential, policy, None),
+        Network::Mainnet => verify_multi_issuer_credential_impl::<MainnetV0>(credential, policy, None),
+    }.map(|_| true).map_err(|e| e.to_string())
+}
+
+/// Verifies the multi-issuer credential like `verify_multi_issuer_credential`, after checking that its
+/// hash is the hash of the attributes in `data` and, given a `reference_time`, that the attributes
+/// are inside their validity window at it.
+#[wasm_bindgen]
+pub fn verify_multi_issuer_credential_attributes(
+    credential: &str,
+    policy: &str,
+    data: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>,
+    reference_time: Option<ReferenceTime>
+) -> Result<bool, String> {
+    let message = Some((data, reference_time, hash_alg, output_type.unwrap_or_default()));
+    match network {
+        Network::Testnet => verify_multi_issuer_credential_impl::<TestnetV0>(credential, policy, message),
+        Network::Mainnet => verify_multi_issuer_credential_impl::<MainnetV0>(credential, policy, message),
+    }.map(|_| true).map_err(|e| e.to_string())
+}
+
+fn issue_delegation_certificate_impl<N: NetworkNative>(private_key: &SecretString, delegate: &str, schemas: Vec<String>, not_before: u64, not_after: u64) -> Result<String, CustomError> {
+    let signer = PrivateKeySigner::<N>::from_secret(private_key)?;
+    let delegate = parse_address_normalized::<N>(delegate)?;
+    Ok(DelegationCertificate::issue(&signer, delegate, schemas, not_before, not_after)?.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for authorising a delegate to issue credentials of the
+/// given schemas between `not_before` and `not_after` (unix seconds). Returns the certificate as JSON.
+#[wasm_bindgen]
+pub fn issue_delegation_certificate(
+    private_key: String,
+    delegate: &str,
+    schemas: Vec<String>,
+    not_before: u64,
+    not_after: u64,
+    network: Network
+) -> Result<String, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => issue_delegation_certificate_impl::<TestnetV0>(&private_key, delegate, schemas, not_before, not_after),
+        Network::Mainnet => issue_delegation_certificate_impl::<MainnetV0>(&private_key, delegate, schemas, not_before, not_after),
+    }.map_err(|e| e.to_string())
+}
+
+/// The options of `sign_delegated_message`: the hashing, and the certificate `chain` ordered from
+/// the root down, checked at `time` (unix seconds) for the credential's `schema` attribute.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct DelegatedSignOptions {
+    pub(crate) hash_alg: HashAlgorithm,
+    pub(crate) output_type: HashOutputType,
+    pub(crate) chain: Vec<String>,
+    pub(crate) time: u64,
+}
+
+#[wasm_bindgen]
+impl DelegatedSignOptions {
+    /// Creates the options, hashing to a field.
+    #[wasm_bindgen(constructor)]
+    pub fn new(hash_alg: HashAlgorithm, chain: Vec<String>, time: u64) -> DelegatedSignOptions {
+        DelegatedSignOptions { hash_alg, output_type: HashOutputType::default(), chain, time }
+    }
+
+    /// Returns the type the hash is mapped to.
+    #[wasm_bindgen(getter, js_name = outputType)]
+    pub fn output_type(&self) -> HashOutputType {
+        self.output_type
+    }
+
+    /// Sets the type the hash is mapped to.
+    #[wasm_bindgen(setter, js_name = outputType)]
+    pub fn set_output_type(&mut self, output_type: HashOutputType) {
+        self.output_type = output_type;
+    }
+}
+
+impl DelegatedSignOptions {
+    // Parses the chain for network `N`.
+    fn parse<N: NetworkNative>(&self) -> Result<DelegationOptions<N>, CustomError> {
+        let chain = self.chain
+            .iter()
+            .map(|certificate| DelegationCertificate::<N>::from_str(certificate))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(DelegationOptions { hash: self.hash_alg, output: self.output_type, chain, time: self.time, checks: SignChecks::default() })
+    }
+}
+
+fn sign_delegated_message_impl<N: NetworkNative>(private_key: &SecretString, message: SignInboundMessage, options: &DelegatedSignOptions) -> Result<String, CustomError> {
+    let signer = PrivateKeySigner::<N>::from_secret(private_key)?;
+    Ok(sign_message_with_delegation(&signer, message, options.parse::<N>()?, &ConsoleLogger)?.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for signing messages as a delegate, attaching the
+/// certificate chain of the `options`. Returns the delegated signature as JSON.
+#[wasm_bindgen]
+pub fn sign_delegated_message(
+    private_key: String,
+    message: SignInboundMessage,
+    options: &DelegatedSignOptions,
+    network: Network
+) -> Result<String, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => sign_delegated_message_impl::<TestnetV0>(&private_key, message, options),
+        Network::Mainnet => sign_delegated_message_impl::<MainnetV0>(&private_key, message, options),
+    }.map_err(|e| e.to_string())
+}
+
+fn verify_delegated_signature_impl<N: NetworkNative>(signature: &str, root: &str, schema: &str, time: u64) -> Result<(), CustomError> {
+    DelegatedSignature::<N>::from_str(signature)?.verify(&parse_address_normalized::<N>(root)?, schema, time)
+}
+
+/// Exposes a Rust function to JavaScript for checking a delegated signature and its chain back to
+/// the trusted `root` address for `schema` at `time` (unix seconds). Use
+/// `verify_delegated_signature_attributes` to also check the credential was signed with that schema.
+#[wasm_bindgen]
+pub fn verify_delegated_signature(signature: &str, root: &str, schema: &str, time: u64, network: Network) -> Result<bool, String> {
+    match network {
+        Network::Testnet => verify_delegated_signature_impl::<TestnetV0>(signature, root, schema, time),
+        Network::Mainnet => verify_delegated_signature_impl::<MainnetV0>(signature, root, schema, time),
+    }.map(|_| true).map_err(|e| e.to_string())
+}
+
+fn verify_delegated_signature_attributes_impl<N: NetworkNative>(signature: &str, root: &str, time: u64, message: SignInboundMessage, hash_alg: HashAlgorithm, output_type: HashOutputType) -> Result<(), CustomError> {
+    DelegatedSignature::<N>::from_str(signature)?
+        .verify_attributes(message, &parse_address_normalized::<N>(root)?, time, hash_alg, output_type, &ConsoleLogger)
+}
+
+/// Verifies the delegated signature like `verify_delegated_signature`, for the `schema` attribute of
+/// `data`, after checking that its hash is the hash of the attributes in `data` and that they are
+/// inside their validity window at the date of `time`.
+#[wasm_bindgen]
+pub fn verify_delegated_signature_attributes(
+    signature: &str,
+    root: &str,
+    time: u64,
+    data: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>
+) -> Result<bool, String> {
+    let output_type = output_type.unwrap_or_default();
+    match network {
+        Network::Testnet => verify_delegated_signature_attributes_impl::<TestnetV0>(signature, root, time, data, hash_alg, output_type),
+        Network::Mainnet => verify_delegated_signature_attributes_impl::<MainnetV0>(signature, root, time, data, hash_alg, output_type),
+    }.map(|_| true).map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for starting an issuer key history with the given key,
+/// active from `activated_at`, in the time basis of the credentials' `issued_at` attribute.
+/// Returns the history as JSON.
+#[wasm_bindgen]
+pub fn new_key_history(private_key: String, activated_at: ReferenceTime, network: Network) -> Result<String, String> {
+    let private_key = SecretString::new(private_key);
+    match network {
+        Network::Testnet => PrivateKeySigner::<TestnetV0>::from_secret(&private_key)
+            .and_then(|signer| KeyHistory::new(&signer, activated_at)).map(|history| history.to_string()),
+        Network::Mainnet => PrivateKeySigner::<MainnetV0>::from_secret(&private_key)
+            .and_then(|signer| KeyHistory::new(&signer, activated_at)).map(|history| history.to_string()),
+    }.map_err(|e| e.to_string())
+}
+
+fn rotate_issuer_key_impl<N: NetworkNative>(history: &str, current_private_key: &SecretString, next: &str, activated_at: ReferenceTime) -> Result<String, CustomError> {
+    let mut history = KeyHistory::<N>::from_str(history)?;
+    let signer = PrivateKeySigner::<N>::from_secret(current_private_key)?;
+    history.rotate(&signer, parse_address_normalized::<N>(next)?, activated_at)?;
+    Ok(history.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for retiring the current issuer key in favour of `next`
+/// at `activated_at`, endorsed by the current key. Returns the updated history as JSON.
+#[wasm_bindgen]
+pub fn rotate_issuer_key(history: &str, current_private_key: String, next: &str, activated_at: ReferenceTime, network: Network) -> Result<String, String> {
+    let current_private_key = SecretString::new(current_private_key);
+    match network {
+        Network::Testnet => rotate_issuer_key_impl::<TestnetV0>(history, &current_private_key, next, activated_at),
+        Network::Mainnet => rotate_issuer_key_impl::<MainnetV0>(history, &current_private_key, next, activated_at),
+    }.map_err(|e| e.to_string())
+}
+
+fn verify_with_key_history_impl<N: NetworkNative>(
+    history: &str,
+    root: &str,
+    data: SignInboundMessage,
+    signature: &str,
+    hash_alg: HashAlgorithm,
+    output_type: HashOutputType
+) -> Result<String, CustomError> {
+    let history = KeyHistory::<N>::from_str(history)?;
+    let signature = Signature::<N>::from_str(signature)
+        .map_err(|e| anyhow!("Failed to parse signature: {}", e))?;
+    Ok(history.verify_signature(&parse_address_normalized::<N>(root)?, data, &signature, hash_alg, output_type, &ConsoleLogger)?.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for checking a credential signature against the issuer
+/// key that was active at the credential's signed `issued_at` attribute, trusting the history from
+/// the `root` address. Returns the address of the signing key.
+#[wasm_bindgen]
+pub fn verify_signature_with_key_history(
+    history: &str,
+    root: &str,
+    data: SignInboundMessage,
+    signature: &str,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>
+) -> Result<String, String> {
+    let output_type = output_type.unwrap_or_default();
+    match network {
+        Network::Testnet => verify_with_key_history_impl::<TestnetV0>(history, root, data, signature, hash_alg, output_type),
+        Network::Mainnet => verify_with_key_history_impl::<MainnetV0>(history, root, data, signature, hash_alg, output_type),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for encoding an ISO-8601 date (`YYYY-MM-DD`) as a `u32` attribute.
+#[wasm_bindgen]
+pub fn encode_date(date: &str, encoding: DateEncoding) -> Result<u32, String> {
+    Date::parse_iso(date).and_then(|date| date.encode(encoding)).map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for decoding a `u32` date attribute into an ISO-8601 date.
+#[wasm_bindgen]
+pub fn decode_date(value: u32, encoding: DateEncoding) -> Result<String, String> {
+    Date::decode(value, encoding).map(|date| date.to_iso()).map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for checking that a string is a valid ISO-8601 calendar date.
+#[wasm_bindgen]
+pub fn is_valid_date(date: &str) -> bool {
+    Date::parse_iso(date).is_ok()
+}
+
+/// Exposes a Rust function to JavaScript for computing the age in whole years on the date `at`
+/// of someone born on `dob`, both ISO-8601 dates.
+#[wasm_bindgen]
+pub fn get_age_at_date(dob: &str, at: &str) -> Result<u32, String> {
+    Date::parse_iso(dob)
+        .and_then(|dob| dob.age_at(&Date::parse_iso(at)?))
+        .map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for computing the encoded latest date of birth of someone
+/// at least `years` old on the ISO-8601 date `at`, to check against with `dob <= threshold` in Leo.
+#[wasm_bindgen]
+pub fn get_age_threshold(at: &str, years: u16, encoding: DateEncoding) -> Result<u32, String> {
+    Date::parse_iso(at)
+        .and_then(|at| at.age_threshold(years))
+        .and_then(|threshold| threshold.encode(encoding))
+        .map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for comparing two encoded dates, as Leo's `value < other` does.
+#[wasm_bindgen]
+pub fn is_date_before(value: u32, other: u32, encoding: DateEncoding) -> Result<bool, String> {
+    is_encoded_date_before(value, other, encoding).map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for encoding a nationality attribute. `code` is an ISO 3166-1
+/// alpha-2, alpha-3 or numeric code, or an English short name. Returns the canonical field.
+#[wasm_bindgen]
+pub fn encode_nationality(code: &str, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => encode_country::<TestnetV0>(code).map(|field| field.to_string()),
+        Network::Mainnet => encode_country::<MainnetV0>(code).map(|field| field.to_string()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for decoding a nationality field into its country, as
+/// `{ alpha2, alpha3, numeric, name }`.
+#[wasm_bindgen]
+pub fn decode_nationality(field: &str, network: Network) -> Result<JsValue, String> {
+    let country = match network {
+        Network::Testnet => Field::<TestnetV0>::from_str(field)
+            .map_err(|e| anyhow!("Invalid field '{}': {}", field, e).into())
+            .and_then(|field| Country::from_field(&field)),
+        Network::Mainnet => Field::<MainnetV0>::from_str(field)
+            .map_err(|e| anyhow!("Invalid field '{}': {}", field, e).into())
+            .and_then(|field| Country::from_field(&field)),
+    }.map_err(|e: CustomError| e.to_string())?;
+    serde_wasm_bindgen::to_value(country).map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for encoding a text attribute (e.g. a name) as `fields`
+/// fields of NFC-normalized, length-prefixed UTF-8, holding at most `max_chars` characters.
+#[wasm_bindgen]
+pub fn encode_text_attribute(text: &str, fields: usize, max_chars: usize, network: Network) -> Result<Vec<String>, String> {
+    let layout = TextAttribute::new(fields, max_chars).map_err(|e| e.to_string())?;
+    match network {
+        Network::Testnet => layout.encode::<TestnetV0>(text).map(|fields| fields.iter().map(|f| f.to_string()).collect()),
+        Network::Mainnet => layout.encode::<MainnetV0>(text).map(|fields| fields.iter().map(|f| f.to_string()).collect()),
+    }.map_err(|e| e.to_string())
+}
+
+fn decode_text_attribute_impl<N: NetworkNative>(fields: &[String]) -> Result<String, CustomError> {
+    let fields = fields
+        .iter()
+        .map(|field| Field::<N>::from_str(field).map_err(|e| anyhow!("Invalid field '{}': {}", field, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    decode_text(&fields)
+}
+
+/// Exposes a Rust function to JavaScript for decoding the fields of a text attribute back into its text.
+#[wasm_bindgen]
+pub fn decode_text_attribute(fields: Vec<String>, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => decode_text_attribute_impl::<TestnetV0>(&fields),
+        Network::Mainnet => decode_text_attribute_impl::<MainnetV0>(&fields),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for encoding a decimal attribute (e.g. `"12.5"`) with
+/// `scale` fractional digits as an integer literal (e.g. `"1250u64"`). Decimals that would need
+/// rounding are rejected.
+#[wasm_bindgen]
+pub fn encode_decimal(decimal: &str, scale: u8, integer_type: DecimalType) -> Result<String, String> {
+    DecimalAttribute::new(scale, integer_type)
+        .and_then(|layout| layout.encode(decimal))
+        .map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for decoding an integer literal back into the decimal
+/// attribute it encodes, with `scale` fractional digits.
+#[wasm_bindgen]
+pub fn decode_decimal(literal: &str, scale: u8, integer_type: DecimalType) -> Result<String, String> {
+    DecimalAttribute::new(scale, integer_type)
+        .and_then(|layout| layout.decode(literal))
+        .map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for computing the hash of the struct `struct_name` defined by
+/// the `.aleo` program source, built out of the credential attributes as the program would hash it.
+#[wasm_bindgen]
+pub fn get_program_struct_hash(
+    program: &str,
+    struct_name: &str,
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>
+) -> Result<String, String> {
+    let output_type = output_type.unwrap_or_default();
+    match network {
+        Network::Testnet => ProgramStructs::<TestnetV0>::from_source(program)
+            .and_then(|program| program.hash(struct_name, &message.data, hash_alg, output_type)),
+        Network::Mainnet => ProgramStructs::<MainnetV0>::from_source(program)
+            .and_then(|program| program.hash(struct_name, &message.data, hash_alg, output_type)),
+    }.map_err(|e| e.to_string())
+}
+
+fn sign_program_message_impl<N: NetworkNative>(
+    private_key: &SecretString,
+    program: &str,
+    struct_name: &str,
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    output_type: HashOutputType
+) -> Result<(String, String), CustomError> {
+    let program = ProgramStructs::<N>::from_source(program)?;
+    let signer = PrivateKeySigner::<N>::from_secret(private_key)?;
+    sign_program_struct(&signer, &program, struct_name, message, hash_alg, output_type, &SignChecks::default(), &ConsoleLogger)
+}
+
+/// Exposes a Rust function to JavaScript for signing the struct `struct_name` defined by the `.aleo`
+/// program source (e.g. `FullCredentials` of `verify_poseidon2_zpass.aleo`). The credential attributes
+/// are type-checked against the struct and hashed in its member order, as the program does.
+/// Returns the response as `SignResponse` or a `JsValue` error.
+#[wasm_bindgen]
+pub fn sign_message_for_program(
+    private_key: String,
+    program: &str,
+    struct_name: &str,
+    message: SignInboundMessage,
+    hash_alg: HashAlgorithm,
+    network: Network,
+    output_type: Option<HashOutputType>
+) -> Result<SignResponse, JsValue> {
+    let private_key = SecretString::new(private_key);
+    let output_type = output_type.unwrap_or_default();
+    let result = match network {
+        Network::Testnet => sign_program_message_impl::<TestnetV0>(&private_key, program, struct_name, message, hash_alg, output_type),
+        Network::Mainnet => sign_program_message_impl::<MainnetV0>(&private_key, program, struct_name, message, hash_alg, output_type),
+    };
+
+    result
+        .map(|(signature, hash)| SignResponse::new(signature, hash))
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Exposes a Rust function to JavaScript for generating the ordered input strings of a transition of a
+/// zPass program variant (e.g. `issue` of `verify_poseidon2_zpass`) out of the credential attributes, the
+/// issuer signature and the salt. Inputs the program fills in from `self.caller` are left out.
+#[wasm_bindgen]
+pub fn get_transition_inputs(
+    program: &str,
+    transition: &str,
+    message: SignInboundMessage,
+    signature: &str,
+    salt: Option<String>,
+    network: Network
+) -> Result<Vec<String>, String> {
+    match network {
+        Network::Testnet => transition_inputs::<TestnetV0>(program, transition, &message.data, signature, salt.as_deref()),
+        Network::Mainnet => transition_inputs::<MainnetV0>(program, transition, &message.data, signature, salt.as_deref()),
+    }.map_err(|e| e.to_string())
+}
+
+/// Exposes a Rust function to JavaScript listing the transitions `get_transition_inputs` supports,
+/// as `program/transition`.
+#[wasm_bindgen]
+pub fn get_supported_transitions() -> Vec<String> {
+    supported_transitions()
+}
+
+/// Exposes a Rust function to JavaScript for generating a random credential salt, e.g. `1234scalar`.
+#[wasm_bindgen]
+pub fn new_credential_salt(network: Network) -> String {
+    match network {
+        Network::Testnet => generate_salt::<TestnetV0>().to_string(),
+        Network::Mainnet => generate_salt::<MainnetV0>().to_string(),
+    }
+}
+
+fn get_issuance_commitment_impl<N: NetworkNative>(subject: &str, salt: &str) -> Result<String, CustomError> {
+    let subject = parse_address_normalized::<N>(subject)?;
+    let salt = parse_salt::<N>(salt)?;
+    issuance_commitment(&subject, &salt).map(|commitment| commitment.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for computing the issuance commitment of the subject and salt,
+/// `BHP256::commit_to_group(subject, salt)`, i.e. the `issued` mapping key the `issue` transitions set.
+#[wasm_bindgen]
+pub fn get_issuance_commitment(subject: &str, salt: &str, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => get_issuance_commitment_impl::<TestnetV0>(subject, salt),
+        Network::Mainnet => get_issuance_commitment_impl::<MainnetV0>(subject, salt),
+    }.map_err(|e| e.to_string())
+}
+
+fn get_hiding_commitments_impl<N: NetworkNative>(data: &JsonValue, salt: &str) -> Result<String, CustomError> {
+    let commitments: serde_json::Map<String, JsonValue> = hiding_commitments::<N>(data, &parse_salt::<N>(salt)?)?
+        .into_iter()
+        .map(|(name, commitment)| (name, JsonValue::String(commitment.to_string())))
+        .collect();
+    Ok(JsonValue::Object(commitments).to_string())
+}
+
+/// Exposes a Rust function to JavaScript for computing the attribute commitments of a `zpass_hiding`
+/// record, as the JSON object `{ issuer, dob, nationality, expiry }` of groups committed with the salt.
+#[wasm_bindgen]
+pub fn get_hiding_commitments(message: SignInboundMessage, salt: &str, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => get_hiding_commitments_impl::<TestnetV0>(&message.data, salt),
+        Network::Mainnet => get_hiding_commitments_impl::<MainnetV0>(&message.data, salt),
+    }.map_err(|e| e.to_string())
+}
+
+fn verify_commitment_opening_impl<N: NetworkNative>(commitment: &str, value: &str, salt: &str) -> Result<bool, CustomError> {
+    let commitment = Group::<N>::from_str(commitment).map_err(|e| anyhow!("Invalid commitment '{}': {}", commitment, e))?;
+    let value = Literal::<N>::from_str(value).map_err(|e| anyhow!("Invalid value '{}': {}", value, e))?;
+    verify_opening(&commitment, &value, &parse_salt::<N>(salt)?)
+}
+
+/// Exposes a Rust function to JavaScript for verifying the opening of a record's attribute commitment:
+/// whether `BHP256::commit_to_group(value, salt)` is `commitment`. `value` is a typed literal, e.g. `20000101u32`.
+#[wasm_bindgen]
+pub fn verify_commitment_opening(commitment: &str, value: &str, salt: &str, network: Network) -> Result<bool, String> {
+    match network {
+        Network::Testnet => verify_commitment_opening_impl::<TestnetV0>(commitment, value, salt),
+        Network::Mainnet => verify_commitment_opening_impl::<MainnetV0>(commitment, value, salt),
+    }.map_err(|e| e.to_string())
+}
+
+fn get_invalidation_commitment_impl<N: NetworkNative>(data: &JsonValue, salt: &str, hiding: bool) -> Result<String, CustomError> {
+    let salt = parse_salt::<N>(salt)?;
+    match hiding {
+        true => hiding_invalidation_commitment(data, &salt),
+        false => invalidation_commitment(data, &salt),
+    }.map(|commitment| commitment.to_string())
+}
+
+/// Exposes a Rust function to JavaScript for computing the invalidation commitment of a credential and salt,
+/// i.e. the `invalidated` mapping key of `zpass_invalidate`, or of `zpass_invalidate_hiding` when `hiding` is set.
+#[wasm_bindgen]
+pub fn get_invalidation_commitment(message: SignInboundMessage, salt: &str, hiding: bool, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => get_invalidation_commitment_impl::<TestnetV0>(&message.data, salt, hiding),
+        Network::Mainnet => get_invalidation_commitment_impl::<MainnetV0>(&message.data, salt, hiding),
+    }.map_err(|e| e.to_string())
+}
+
+fn parse_zpass_record_impl<N: NetworkNative>(record: &str, variant: Option<ZPassVariant>) -> Result<String, CustomError> {
+    match variant {
+        Some(variant) => ZPassRecord::<N>::parse_variant(record, variant),
+        None => ZPassRecord::<N>::from_str(record),
+    }?.to_json()
+}
+
+/// Exposes a Rust function to JavaScript for parsing a decrypted `ZPass` record, as returned by
+/// `getZPassRecord`, into a JSON object of its owner, `variant`, typed attributes and nonce.
+/// The variant is detected from the record entries unless given.
+#[wasm_bindgen]
+pub fn parse_zpass_record(record: &str, variant: Option<ZPassVariant>, network: Network) -> Result<String, String> {
+    match network {
+        Network::Testnet => parse_zpass_record_impl::<TestnetV0>(record, variant),
+        Network::Mainnet => parse_zpass_record_impl::<MainnetV0>(record, variant),
+    }.map_err(|e| e.to_string())
+}
+
+fn is_zpass_record_issued_by_impl<N: NetworkNative>(record: &str, issuer: &str) -> Result<bool, CustomError> {
+    let issuer = parse_address_normalized::<N>(issuer)?;
+    ZPassRecord::<N>::from_str(record)?.is_issued_by(&issuer)
+}
+
+/// Exposes a Rust function to JavaScript for checking a decrypted `ZPass` record was issued by `issuer`,
+/// opening the issuer commitment of hiding records with their salt.
+#[wasm_bindgen]
+pub fn is_zpass_record_issued_by(record: &str, issuer: &str, network: Network) -> Result<bool, String> {
+    match network {
+        Network::Testnet => is_zpass_record_issued_by_impl::<TestnetV0>(record, issuer),
+        Network::Mainnet => is_zpass_record_issued_by_impl::<MainnetV0>(record, issuer),
+    }.map_err(|e| e.to_string())
+}